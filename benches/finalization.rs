@@ -0,0 +1,79 @@
+//! Compares two ways of weighting the lanes of the SIMD kernel into `b`, on `u16` lanes as in
+//! `Fletcher32`. These are standalone copies of the kernel's loop, not the crate's own code.
+//!
+//! * `final_weighting`, as in `update_fletcher_simd`: the loop only adds, and the lane positions
+//!   are weighted in once at the end with `LANES * b_accum[i] - i * a_accum[i]`.
+//! * `incremental_weighting`: every vector adds its lane-weighted contribution to `b` in the
+//!   loop, which leaves plain horizontal sums at the end.
+
+#![feature(portable_simd)]
+
+use {
+    core::simd::{Simd, SimdUint},
+    criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput},
+};
+
+const LANES: usize = 16;
+
+type Vector = Simd<u16, LANES>;
+
+fn final_weighting(vecs: &[Vector]) -> (u16, u16) {
+    let mut a_accum = Vector::default();
+    let mut b_accum = Vector::default();
+
+    for &vec in vecs {
+        a_accum += vec;
+        b_accum += a_accum;
+    }
+
+    let increasing = Vector::from_array(core::array::from_fn(|i| i as u16));
+    let lanes_shift = Vector::splat(LANES.trailing_zeros() as u16);
+    let b = ((b_accum << lanes_shift) - a_accum * increasing).reduce_sum();
+
+    (a_accum.reduce_sum(), b)
+}
+
+fn incremental_weighting(vecs: &[Vector]) -> (u16, u16) {
+    let mut a_accum = Vector::default();
+    let mut b_accum = Vector::default();
+
+    // Lane `i` of a vector is followed by `LANES - i` blocks of the same vector, counting
+    // itself, and every earlier block in the lane is followed by all `LANES` of them.
+    let decreasing = Vector::from_array(core::array::from_fn(|i| (LANES - i) as u16));
+    let lanes_shift = Vector::splat(LANES.trailing_zeros() as u16);
+
+    for &vec in vecs {
+        b_accum += (a_accum << lanes_shift) + vec * decreasing;
+        a_accum += vec;
+    }
+
+    (a_accum.reduce_sum(), b_accum.reduce_sum())
+}
+
+fn bench_finalization(c: &mut Criterion) {
+    let mut group = c.benchmark_group("finalization");
+
+    for size in [64, 1024, 1024 * 1024] {
+        let vecs: Vec<Vector> = (0..size / (LANES * 2))
+            .map(|v| Vector::from_array(core::array::from_fn(|i| (v * LANES + i) as u16)))
+            .collect();
+        assert_eq!(final_weighting(&vecs), incremental_weighting(&vecs));
+
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(
+            BenchmarkId::new("final_weighting", size),
+            &vecs,
+            |b, vecs| b.iter(|| final_weighting(black_box(vecs))),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("incremental_weighting", size),
+            &vecs,
+            |b, vecs| b.iter(|| incremental_weighting(black_box(vecs))),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_finalization);
+criterion_main!(benches);
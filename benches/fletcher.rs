@@ -0,0 +1,340 @@
+use core::convert::TryInto;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use fletcher_simd::{Endianness, Fletcher128, Fletcher16, Fletcher32, Fletcher64};
+
+/// Input sizes in bytes. The smallest is not a multiple of any vector size, so that calls are
+/// dominated by overhead and the scalar ends.
+const SIZES: [usize; 4] = [24, 64, 1024, 1024 * 1024];
+
+macro_rules! bench_fletcher {
+    ($fn_name:ident, $fletcher:ty, $block_type:ty, $name:literal $(, $slice_method:ident)*) => {
+        fn $fn_name(c: &mut Criterion) {
+            let mut group = c.benchmark_group($name);
+
+            for size in SIZES {
+                let data: Vec<$block_type> = (0..size / core::mem::size_of::<$block_type>())
+                    .map(|i| i as $block_type)
+                    .collect();
+
+                group.throughput(Throughput::Bytes(size as u64));
+
+                group.bench_with_input(
+                    BenchmarkId::new("update_with_slice", size),
+                    &data,
+                    |b, data| {
+                        b.iter(|| {
+                            let mut fletcher = <$fletcher>::new();
+                            fletcher.update_with_slice(black_box(data));
+                            fletcher.value()
+                        })
+                    },
+                );
+
+                $(
+                    group.bench_with_input(
+                        BenchmarkId::new(stringify!($slice_method), size),
+                        &data,
+                        |b, data| {
+                            b.iter(|| {
+                                let mut fletcher = <$fletcher>::new();
+                                fletcher.$slice_method(black_box(data));
+                                fletcher.value()
+                            })
+                        },
+                    );
+                )*
+
+                group.bench_with_input(
+                    BenchmarkId::new("update_with_iter", size),
+                    &data,
+                    |b, data| {
+                        b.iter(|| {
+                            let mut fletcher = <$fletcher>::new();
+                            fletcher.update_with_iter(black_box(data).iter().copied());
+                            fletcher.value()
+                        })
+                    },
+                );
+
+                // Hides the length of the iterator, so that it takes the path for iterators
+                // without a known length.
+                group.bench_with_input(
+                    BenchmarkId::new("update_with_iter_unsized", size),
+                    &data,
+                    |b, data| {
+                        b.iter(|| {
+                            let mut fletcher = <$fletcher>::new();
+                            fletcher.update_with_iter(
+                                black_box(data).iter().copied().filter(|_| true),
+                            );
+                            fletcher.value()
+                        })
+                    },
+                );
+
+                group.bench_with_input(
+                    BenchmarkId::new("update_with_iter_scalar", size),
+                    &data,
+                    |b, data| {
+                        b.iter(|| {
+                            let mut fletcher = <$fletcher>::new();
+                            fletcher.update_with_iter_scalar(black_box(data).iter().copied());
+                            fletcher.value()
+                        })
+                    },
+                );
+            }
+
+            group.finish();
+        }
+    };
+}
+
+bench_fletcher!(
+    bench_fletcher16,
+    Fletcher16,
+    u8,
+    "fletcher16",
+    update_with_slice_swar
+);
+bench_fletcher!(bench_fletcher32, Fletcher32, u16, "fletcher32");
+bench_fletcher!(bench_fletcher64, Fletcher64, u32, "fletcher64");
+bench_fletcher!(bench_fletcher128, Fletcher128, u64, "fletcher128");
+
+/// Compares the default 256-bit vectors against 128-bit ones, the register width of SSE, Neon
+/// and SIMD128. Where the registers are narrower than the vectors, each vector is split across
+/// two of them, which gives two independent accumulators rather than a slower emulation.
+macro_rules! bench_lanes {
+    ($fn_name:ident, $fletcher:ty, $block_type:ty, $name:literal, $narrow_lanes:literal) => {
+        fn $fn_name(c: &mut Criterion) {
+            let mut group = c.benchmark_group($name);
+
+            for size in SIZES {
+                let data: Vec<$block_type> = (0..size / core::mem::size_of::<$block_type>())
+                    .map(|i| i as $block_type)
+                    .collect();
+
+                group.throughput(Throughput::Bytes(size as u64));
+
+                group.bench_with_input(BenchmarkId::new("256_bit", size), &data, |b, data| {
+                    b.iter(|| {
+                        let mut fletcher = <$fletcher>::new();
+                        fletcher.update_with_slice_lanes::<{ <$fletcher>::LANES }>(black_box(data));
+                        fletcher.value()
+                    })
+                });
+
+                group.bench_with_input(BenchmarkId::new("128_bit", size), &data, |b, data| {
+                    b.iter(|| {
+                        let mut fletcher = <$fletcher>::new();
+                        fletcher.update_with_slice_lanes::<$narrow_lanes>(black_box(data));
+                        fletcher.value()
+                    })
+                });
+            }
+
+            group.finish();
+        }
+    };
+}
+
+bench_lanes!(bench_lanes16, Fletcher16, u8, "lanes/fletcher16", 16);
+bench_lanes!(bench_lanes32, Fletcher32, u16, "lanes/fletcher32", 8);
+bench_lanes!(bench_lanes64, Fletcher64, u32, "lanes/fletcher64", 4);
+bench_lanes!(bench_lanes128, Fletcher128, u64, "lanes/fletcher128", 2);
+
+/// Byte input, grouped into blocks in each byte order, against decoding it into blocks in a
+/// separate pass and summing those.
+macro_rules! bench_bytes {
+    ($fn_name:ident, $fletcher:ty, $block_type:ty, $name:literal) => {
+        fn $fn_name(c: &mut Criterion) {
+            let mut group = c.benchmark_group($name);
+
+            for size in SIZES {
+                let data: Vec<u8> = (0..size).map(|i| i as u8).collect();
+
+                group.throughput(Throughput::Bytes(size as u64));
+
+                for (id, endianness) in [("little", Endianness::Little), ("big", Endianness::Big)] {
+                    group.bench_with_input(
+                        BenchmarkId::new(format!("update_with_bytes_{}", id), size),
+                        &data,
+                        |b, data| {
+                            b.iter(|| {
+                                let mut fletcher = <$fletcher>::new();
+                                fletcher.update_with_bytes(black_box(data), endianness);
+                                fletcher.value()
+                            })
+                        },
+                    );
+                }
+
+                group.bench_with_input(
+                    BenchmarkId::new("decode_then_update_with_slice", size),
+                    &data,
+                    |b, data| {
+                        b.iter(|| {
+                            let blocks: Vec<$block_type> = black_box(data)
+                                .chunks_exact(core::mem::size_of::<$block_type>())
+                                .map(|chunk| {
+                                    <$block_type>::from_le_bytes(chunk.try_into().unwrap())
+                                })
+                                .collect();
+                            let mut fletcher = <$fletcher>::new();
+                            fletcher.update_with_slice(&blocks);
+                            fletcher.value()
+                        })
+                    },
+                );
+            }
+
+            group.finish();
+        }
+    };
+}
+
+bench_bytes!(bench_bytes32, Fletcher32, u16, "bytes/fletcher32");
+bench_bytes!(bench_bytes64, Fletcher64, u32, "bytes/fletcher64");
+bench_bytes!(bench_bytes128, Fletcher128, u64, "bytes/fletcher128");
+
+/// Fixed-size records, e.g. an IPv4 header and a cache line, whose length is known at compile
+/// time.
+fn bench_records(c: &mut Criterion) {
+    let mut group = c.benchmark_group("records/fletcher16");
+
+    macro_rules! bench_record {
+        ($len:literal) => {
+            let data: [u8; $len] = core::array::from_fn(|i| i as u8);
+
+            group.throughput(Throughput::Bytes($len));
+
+            group.bench_with_input(
+                BenchmarkId::new("update_with_slice", $len),
+                &data,
+                |b, data| {
+                    b.iter(|| {
+                        let mut fletcher = Fletcher16::new();
+                        fletcher.update_with_slice(black_box(&data[..]));
+                        fletcher.value()
+                    })
+                },
+            );
+
+            group.bench_with_input(BenchmarkId::new("update_array", $len), &data, |b, data| {
+                b.iter(|| {
+                    let mut fletcher = Fletcher16::new();
+                    fletcher.update_array(black_box(data));
+                    fletcher.value()
+                })
+            });
+        };
+    }
+
+    bench_record!(20);
+    bench_record!(64);
+
+    group.finish();
+}
+
+/// Input sizes in bytes around `PAR_CHUNK_BYTES`, below which `par_update_with_slice` stays
+/// serial, to find where checksumming on the thread pool starts to pay off.
+#[cfg(feature = "rayon")]
+const PAR_SIZES: [usize; 6] = [
+    64 * 1024,
+    128 * 1024,
+    256 * 1024,
+    512 * 1024,
+    1024 * 1024,
+    4 * 1024 * 1024,
+];
+
+/// Serial against parallel updates, over the same chunking as `par_update_with_slice` but with the
+/// whole slice handed to the thread pool, so that the crossover is visible below `PAR_CHUNK_BYTES`
+/// too.
+#[cfg(feature = "rayon")]
+fn bench_par(c: &mut Criterion) {
+    use rayon::prelude::*;
+
+    let mut group = c.benchmark_group("par/fletcher16");
+
+    for size in PAR_SIZES {
+        let data: Vec<u8> = (0..size).map(|i| i as u8).collect();
+
+        group.throughput(Throughput::Bytes(size as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("update_with_slice", size),
+            &data,
+            |b, data| {
+                b.iter(|| {
+                    let mut fletcher = Fletcher16::new();
+                    fletcher.update_with_slice(black_box(data));
+                    fletcher.value()
+                })
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("par_update_with_slice", size),
+            &data,
+            |b, data| {
+                b.iter(|| {
+                    let mut fletcher = Fletcher16::new();
+                    fletcher.par_update_with_slice(black_box(data));
+                    fletcher.value()
+                })
+            },
+        );
+
+        // Split into as many chunks as there are threads, regardless of `PAR_CHUNK_BYTES`.
+        let chunk_len = (size / rayon::current_num_threads()).max(1);
+        group.bench_with_input(
+            BenchmarkId::new("par_chunks_combine", size),
+            &data,
+            |b, data| {
+                b.iter(|| {
+                    let (fletcher, _) = black_box(data)
+                        .par_chunks(chunk_len)
+                        .map(|chunk| {
+                            let mut fletcher = Fletcher16::new();
+                            fletcher.update_with_slice(chunk);
+                            (fletcher, chunk.len())
+                        })
+                        .reduce(
+                            || (Fletcher16::new(), 0),
+                            |(mut acc, acc_len), (other, other_len)| {
+                                acc.combine(&other, other_len);
+                                (acc, acc_len + other_len)
+                            },
+                        );
+                    fletcher.value()
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_records,
+    bench_fletcher16,
+    bench_fletcher32,
+    bench_fletcher64,
+    bench_fletcher128,
+    bench_lanes16,
+    bench_lanes32,
+    bench_lanes64,
+    bench_lanes128,
+    bench_bytes32,
+    bench_bytes64,
+    bench_bytes128
+);
+#[cfg(feature = "rayon")]
+criterion_group!(par_benches, bench_par);
+
+#[cfg(not(feature = "rayon"))]
+criterion_main!(benches);
+#[cfg(feature = "rayon")]
+criterion_main!(benches, par_benches);
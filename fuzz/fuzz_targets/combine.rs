@@ -0,0 +1,27 @@
+#![no_main]
+
+use fletcher_simd::Fletcher16;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // Use the first four bytes to choose where to split the rest of the input, so that every
+    // split of any input the fuzzer generates is reachable.
+    if data.len() < 4 {
+        return;
+    }
+    let (split, data) = data.split_at(4);
+    let split =
+        u32::from_le_bytes([split[0], split[1], split[2], split[3]]) as usize % (data.len() + 1);
+    let (first, second) = data.split_at(split);
+
+    let mut whole = Fletcher16::new();
+    whole.update_with_slice(data);
+
+    let mut combined = Fletcher16::new();
+    combined.update_with_slice(first);
+    let mut other = Fletcher16::new();
+    other.update_with_slice(second);
+    combined.combine(&other, second.len());
+
+    assert_eq!(whole, combined);
+});
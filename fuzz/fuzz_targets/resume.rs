@@ -0,0 +1,18 @@
+#![no_main]
+
+use fletcher_simd::Fletcher32;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (Fletcher32, Vec<u16>)| {
+    let (state, data) = input;
+
+    // A state persisted as its value and resumed must checksum the rest of the input the same way
+    // as the original, whatever its sums.
+    let mut resumed = Fletcher32::from_value(state.value());
+    assert_eq!(resumed, state);
+
+    let mut original = state;
+    original.update_with_slice(&data);
+    resumed.update_with_iter_scalar(data.iter().copied());
+    assert_eq!(original, resumed);
+});
@@ -0,0 +1,23 @@
+#![no_main]
+
+use fletcher_simd::{Fletcher16, Fletcher64};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mut simd = Fletcher16::new();
+    let mut scalar = Fletcher16::new();
+    simd.update_with_slice(data);
+    scalar.update_with_iter_scalar(data.iter().copied());
+    assert_eq!(simd, scalar);
+
+    let blocks: Vec<u32> = data
+        .chunks_exact(4)
+        .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect();
+
+    let mut simd = Fletcher64::new();
+    let mut scalar = Fletcher64::new();
+    simd.update_with_slice(&blocks);
+    scalar.update_with_iter_scalar(blocks.iter().copied());
+    assert_eq!(simd, scalar);
+});
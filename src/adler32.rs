@@ -0,0 +1,78 @@
+//! The Adler-32 checksum, backed by the same SIMD accumulation as [`Fletcher`](crate::Fletcher).
+
+use crate::{sum_widened, MAX_VEC_SIZE};
+
+/// The largest prime smaller than `2^16`, which Adler-32 reduces both sums by.
+const MOD_ADLER: u64 = 65521;
+
+/// Maximum number of bytes that are summed before reducing.
+///
+/// Each run is summed from zero in `u32` lanes, so the run length `n` must satisfy
+/// `255 * n * (n + 1) / 2 <= u32::MAX` for `b` to stay exact.
+const MAX_RUN_LEN: usize = 5802;
+
+/// Number of `u32` lanes the bytes are widened into.
+const NUM_LANES: usize = MAX_VEC_SIZE / 4;
+
+/// An Adler-32 checksum object that allows for continuous updates to the checksum.
+///
+/// Adler-32 is Fletcher-32 over bytes with `a` seeded to 1 and both sums reduced modulo 65521, as
+/// specified in [RFC 1950] and used by zlib.
+///
+/// # Examples
+///
+/// ```
+/// use fletcher_simd::Adler32;
+///
+/// let mut adler = Adler32::new();
+/// adler.update_with_slice(b"Wikipedia");
+///
+/// assert_eq!(adler.value(), 0x11E60398);
+/// ```
+///
+/// [RFC 1950]: https://www.rfc-editor.org/rfc/rfc1950
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Adler32 {
+    /// Constructs a new `Adler32` with `a` seeded to 1.
+    pub fn new() -> Self {
+        Self { a: 1, b: 0 }
+    }
+
+    /// Updates the checksum with a slice of bytes.
+    pub fn update_with_slice(&mut self, data: &[u8]) {
+        let mut a = self.a as u64;
+        let mut b = self.b as u64;
+
+        for run in data.chunks(MAX_RUN_LEN) {
+            let (run_a, run_b) = sum_widened::<u8, u32, NUM_LANES>(run);
+
+            b = (b + run.len() as u64 * a + run_b as u64) % MOD_ADLER;
+            a = (a + run_a as u64) % MOD_ADLER;
+        }
+
+        self.a = a as u32;
+        self.b = b as u32;
+    }
+
+    /// Returns the checksum value.
+    pub fn value(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Adler32> for u32 {
+    fn from(adler: Adler32) -> Self {
+        adler.value()
+    }
+}
@@ -0,0 +1,120 @@
+//! A checksum whose width is chosen at runtime.
+
+use {
+    crate::{Endianness, Fletcher128, Fletcher16, Fletcher32, Fletcher64},
+    core::convert::TryFrom,
+};
+
+/// A checksum of any of the widths up to 128 bits, for when the width is only known at runtime,
+/// e.g. from a command-line flag.
+///
+/// Every method dispatches to the checksum it wraps. The input is always bytes, which are grouped
+/// into blocks in the byte order [`Endianness::DEFAULT`] as with
+/// [`update_with_bytes`](crate::Fletcher::update_with_bytes).
+///
+/// # Examples
+///
+/// ```
+/// use fletcher_simd::FletcherAny;
+///
+/// let mut fletcher = FletcherAny::new(16).unwrap();
+/// fletcher.update_with_slice(b"abcde");
+///
+/// assert_eq!(fletcher.value(), 0xC3EF);
+/// assert!(FletcherAny::new(24).is_none());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FletcherAny {
+    /// A 16-bit checksum.
+    Fletcher16(Fletcher16),
+    /// A 32-bit checksum.
+    Fletcher32(Fletcher32),
+    /// A 64-bit checksum.
+    Fletcher64(Fletcher64),
+    /// A 128-bit checksum.
+    Fletcher128(Fletcher128),
+}
+
+impl FletcherAny {
+    /// Constructs a new checksum of the given width in bits with the default values, or `None`
+    /// if the width is not 16, 32, 64 or 128.
+    pub fn new(bits: u16) -> Option<Self> {
+        match bits {
+            16 => Some(Self::Fletcher16(Fletcher16::new())),
+            32 => Some(Self::Fletcher32(Fletcher32::new())),
+            64 => Some(Self::Fletcher64(Fletcher64::new())),
+            128 => Some(Self::Fletcher128(Fletcher128::new())),
+            _ => None,
+        }
+    }
+
+    /// Returns the width of the checksum in bits.
+    pub fn bits(&self) -> u32 {
+        match self {
+            Self::Fletcher16(_) => Fletcher16::RESULT_BITS,
+            Self::Fletcher32(_) => Fletcher32::RESULT_BITS,
+            Self::Fletcher64(_) => Fletcher64::RESULT_BITS,
+            Self::Fletcher128(_) => Fletcher128::RESULT_BITS,
+        }
+    }
+
+    /// Updates the checksum with a slice of bytes, grouped into blocks in the byte order
+    /// [`Endianness::DEFAULT`]. If the length of `data` is not a multiple of the block size, the
+    /// trailing partial block is zero-padded.
+    pub fn update_with_slice(&mut self, data: &[u8]) {
+        match self {
+            Self::Fletcher16(fletcher) => fletcher.update_with_slice(data),
+            Self::Fletcher32(fletcher) => fletcher.update_with_bytes(data, Endianness::DEFAULT),
+            Self::Fletcher64(fletcher) => fletcher.update_with_bytes(data, Endianness::DEFAULT),
+            Self::Fletcher128(fletcher) => fletcher.update_with_bytes(data, Endianness::DEFAULT),
+        }
+    }
+
+    /// Returns the checksum value, zero-extended to a `u128`.
+    #[must_use]
+    pub fn value(&self) -> u128 {
+        match self {
+            Self::Fletcher16(fletcher) => fletcher.value().into(),
+            Self::Fletcher32(fletcher) => fletcher.value().into(),
+            Self::Fletcher64(fletcher) => fletcher.value().into(),
+            Self::Fletcher128(fletcher) => fletcher.value(),
+        }
+    }
+}
+
+/// Selects the width in bits, as with [`FletcherAny::new`].
+impl TryFrom<usize> for FletcherAny {
+    type Error = UnsupportedWidthError;
+
+    fn try_from(bits: usize) -> Result<Self, Self::Error> {
+        u16::try_from(bits)
+            .ok()
+            .and_then(Self::new)
+            .ok_or(UnsupportedWidthError { bits })
+    }
+}
+
+/// Error returned when selecting a [`FletcherAny`] width that is not 16, 32, 64 or 128 bits.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct UnsupportedWidthError {
+    bits: usize,
+}
+
+impl UnsupportedWidthError {
+    /// Returns the rejected width in bits.
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+}
+
+impl core::fmt::Display for UnsupportedWidthError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "width {} is not one of 16, 32, 64 or 128 bits",
+            self.bits
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedWidthError {}
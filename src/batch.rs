@@ -0,0 +1,138 @@
+//! Checksumming many equal-length buffers in lockstep, one buffer per SIMD lane.
+
+use {
+    crate::{Fletcher, FletcherSimdVec, MAX_VEC_SIZE},
+    core::simd::{LaneCount, Simd, SimdElement, SupportedLaneCount},
+    num::traits::{WrappingAdd, WrappingSub},
+};
+
+#[cfg(feature = "runtime_dispatch")]
+use multiversion::multiversion;
+// The ARM feature detection macros that `multiversion` names are not in the prelude, see the
+// crate root.
+#[cfg(all(feature = "runtime_dispatch", target_arch = "aarch64"))]
+use std::arch::is_aarch64_feature_detected;
+#[cfg(all(feature = "runtime_dispatch", target_arch = "arm"))]
+use std::arch::is_arm_feature_detected;
+
+/// Transposes a tile of `LANES` vectors, so that lane `i` of vector `j` of the result is lane `j`
+/// of vector `i` of `rows`.
+///
+/// Each round interleaves the first half of the vectors with the second half, and after
+/// `log2(LANES)` rounds every lane has moved to its transposed position.
+#[inline(always)]
+fn transpose<BlockType, const LANES: usize>(
+    mut rows: [Simd<BlockType, LANES>; LANES],
+) -> [Simd<BlockType, LANES>; LANES]
+where
+    BlockType: SimdElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    for _ in 0..LANES.trailing_zeros() {
+        let mut interleaved = rows;
+        for i in 0..LANES / 2 {
+            (interleaved[2 * i], interleaved[2 * i + 1]) = rows[i].interleave(rows[i + LANES / 2]);
+        }
+        rows = interleaved;
+    }
+
+    rows
+}
+
+/// Function that sums `LANES` buffers of the same length at once, with lane `i` of the returned
+/// `a` and `b` vectors holding the sums of buffer `i`.
+///
+/// Unlike [`update_fletcher_simd`](crate::update_fletcher_simd), which spreads the positions of
+/// a single buffer across lanes, every lane here sees every position of its own buffer, so no
+/// reduction is needed at the end. The buffers are read a tile of `LANES` blocks of each at a
+/// time, which is transposed in registers into `LANES` vectors of one position of every buffer.
+// Keep the clones in sync with `dispatched_target_features`.
+#[cfg_attr(feature = "runtime_dispatch", multiversion)]
+#[cfg_attr(feature = "runtime_dispatch", clone(target = "[x86|x86_64]+avx+avx2"))]
+#[cfg_attr(feature = "runtime_dispatch", clone(target = "[x86|x86_64]+avx"))]
+#[cfg_attr(feature = "runtime_dispatch", clone(target = "[x86|x86_64]+sse+sse2"))]
+#[cfg_attr(feature = "runtime_dispatch", clone(target = "[x86|x86_64]+sse"))]
+#[cfg_attr(feature = "runtime_dispatch", clone(target = "[arm|aarch64]+neon"))]
+fn sum_lockstep<BlockType, const LANES: usize>(
+    buffers: &[&[BlockType]],
+) -> (Simd<BlockType, LANES>, Simd<BlockType, LANES>)
+where
+    BlockType: Copy + Clone + Default + SimdElement + WrappingAdd + WrappingSub,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<BlockType, LANES>: FletcherSimdVec<BlockType, LANES>,
+{
+    debug_assert_eq!(buffers.len(), LANES);
+
+    // Slicing every buffer to the same length up front lets the loops below index them without
+    // further bounds checks.
+    let len = buffers[0].len();
+    let buffers: [&[BlockType]; LANES] = core::array::from_fn(|lane| &buffers[lane][..len]);
+
+    let mut a = Simd::<BlockType, LANES>::default();
+    let mut b = Simd::<BlockType, LANES>::default();
+
+    let tiled_len = len - len % LANES;
+    for start in (0..tiled_len).step_by(LANES) {
+        let tile = transpose(core::array::from_fn(|lane| {
+            Simd::from_slice(&buffers[lane][start..start + LANES])
+        }));
+
+        for elem in tile {
+            a += elem;
+            b += a;
+        }
+    }
+
+    for pos in tiled_len..len {
+        let elem = Simd::from_array(core::array::from_fn(|lane| buffers[lane][pos]));
+
+        a += elem;
+        b += a;
+    }
+
+    (a, b)
+}
+
+macro_rules! impl_batch_fletcher {
+    ($name:ident, $result_type:ty, $block_type:ty, $alias:literal) => {
+        #[doc = concat!("Computes the ", $alias, " checksum of each buffer, returning them in order.")]
+        ///
+        /// When every buffer has the same length, groups of as many buffers as there are SIMD
+        /// lanes are checksummed in lockstep with one buffer per lane, which keeps the lanes busy
+        /// even when the buffers are short. Any other buffers, including a final group too small
+        /// to fill the lanes, are checksummed on their own as with
+        #[doc = concat!("[`", $alias, "::checksum_each`](crate::", $alias, "::checksum_each).")]
+        pub fn $name(buffers: &[&[$block_type]]) -> Vec<$result_type> {
+            const LANES: usize = MAX_VEC_SIZE / core::mem::size_of::<$block_type>();
+
+            if buffers
+                .windows(2)
+                .any(|pair| pair[0].len() != pair[1].len())
+            {
+                return Fletcher::<$result_type>::checksum_each(buffers.iter().copied());
+            }
+
+            buffers
+                .chunks(LANES)
+                .flat_map(|group| {
+                    if group.len() < LANES {
+                        return Fletcher::<$result_type>::checksum_each(group.iter().copied());
+                    }
+
+                    let (a, b) = sum_lockstep::<$block_type, LANES>(group);
+
+                    (0..LANES)
+                        .map(|lane| {
+                            Fletcher::<$result_type>::with_initial_values(a[lane], b[lane]).value()
+                        })
+                        .collect()
+                })
+                .collect()
+        }
+    };
+}
+
+impl_batch_fletcher!(batch_fletcher16, u16, u8, "Fletcher16");
+impl_batch_fletcher!(batch_fletcher32, u32, u16, "Fletcher32");
+impl_batch_fletcher!(batch_fletcher64, u64, u32, "Fletcher64");
+impl_batch_fletcher!(batch_fletcher128, u128, u64, "Fletcher128");
@@ -0,0 +1,152 @@
+//! Fletcher checksums that carry a partial SIMD vector between updates.
+
+use {
+    crate::{update_fletcher_simd, Fletcher, FletcherChecksum, MAX_VEC_SIZE},
+    core::simd::{LaneCount, Simd, SupportedLaneCount},
+};
+
+/// A Fletcher checksum object that holds back a trailing partial SIMD vector of blocks until the
+/// next update fills it.
+///
+/// [`Fletcher::update_with_iter`] checksums whatever is left over after the last full vector as
+/// scalars, so many short updates mostly run the scalar path. This buffers the leftover blocks
+/// instead, so that consecutive updates coalesce into full vectors. The checksum is the same as
+/// that of a single update over all of the blocks.
+///
+/// # Examples
+///
+/// ```
+/// use fletcher_simd::{BufferedFletcher, Fletcher32};
+///
+/// let mut buffered = BufferedFletcher::<u32>::new();
+/// let mut fletcher = Fletcher32::new();
+///
+/// for i in 0..100u16 {
+///     buffered.update_with_iter(0..i);
+///     fletcher.update_with_iter(0..i);
+/// }
+///
+/// assert_eq!(buffered.value(), fletcher.value());
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct BufferedFletcher<T: FletcherChecksum> {
+    fletcher: Fletcher<T>,
+    // Sized for the narrowest block, so only the first vector's worth is used by wider ones.
+    pending: [T::BlockType; MAX_VEC_SIZE],
+    pending_len: usize,
+}
+
+/// Overwrites both sums and the held-back blocks with zeroes, which leaves the checksum in its
+/// default state.
+#[cfg(feature = "zeroize")]
+impl<T> zeroize::Zeroize for BufferedFletcher<T>
+where
+    T: FletcherChecksum,
+    T::BlockType: zeroize::Zeroize,
+    Fletcher<T>: zeroize::Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.fletcher.zeroize();
+        self.pending.zeroize();
+        self.pending_len.zeroize();
+    }
+}
+
+macro_rules! impl_buffered_fletcher {
+    ($result_type:ty, $block_type:ty, $block_size:expr) => {
+        impl BufferedFletcher<$result_type> {
+            /// Number of blocks in each SIMD vector.
+            const LANES: usize = MAX_VEC_SIZE / $block_size;
+
+            /// Constructs a new `BufferedFletcher<T>` with the default values.
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// Updates the checksum with an iterator over elements of type `T::BlockType`,
+            /// holding back any blocks that do not fill a whole SIMD vector.
+            pub fn update_with_iter<Iter>(&mut self, elems: Iter)
+            where
+                Iter: Iterator<Item = $block_type>,
+            {
+                self.update_with_iter_lanes::<Iter, { MAX_VEC_SIZE / $block_size }>(elems);
+            }
+
+            fn update_with_iter_lanes<Iter, const LANES: usize>(&mut self, elems: Iter)
+            where
+                Iter: Iterator<Item = $block_type>,
+                LaneCount<LANES>: SupportedLaneCount,
+            {
+                let pending = &mut self.pending[..LANES];
+                let pending_len = &mut self.pending_len;
+
+                (self.fletcher.a, self.fletcher.b) = update_fletcher_simd(
+                    self.fletcher.a,
+                    self.fletcher.b,
+                    elems.filter_map(|elem| {
+                        pending[*pending_len] = elem;
+                        *pending_len += 1;
+
+                        if *pending_len == LANES {
+                            *pending_len = 0;
+                            Some(Simd::<$block_type, LANES>::from_slice(pending))
+                        } else {
+                            None
+                        }
+                    }),
+                );
+            }
+
+            /// Updates the checksum with a slice of data of type `T::BlockType`, holding back
+            /// any blocks that do not fill a whole SIMD vector.
+            pub fn update_with_slice(&mut self, mut data: &[$block_type]) {
+                if self.pending_len > 0 {
+                    let fill_len = (Self::LANES - self.pending_len).min(data.len());
+                    let (fill, rest) = data.split_at(fill_len);
+                    self.update_with_iter(fill.iter().copied());
+                    data = rest;
+                }
+
+                let (whole, rest) = data.split_at(data.len() - data.len() % Self::LANES);
+                self.fletcher.update_with_slice(whole);
+                self.update_with_iter(rest.iter().copied());
+            }
+
+            /// Resets the checksum in place to the state reconstructed from its value by
+            /// [`Fletcher::from_value`], discarding any held-back blocks.
+            pub fn reset_with_value(&mut self, value: $result_type) {
+                self.fletcher.reset_with_value(value);
+                self.pending = [0; MAX_VEC_SIZE];
+                self.pending_len = 0;
+            }
+
+            /// Returns the checksum of every block so far, including the ones held back.
+            pub fn fletcher(&self) -> Fletcher<$result_type> {
+                let mut fletcher = self.fletcher;
+                fletcher.update_with_iter_scalar(self.pending[..self.pending_len].iter().copied());
+                fletcher
+            }
+
+            /// Returns the checksum value.
+            #[must_use]
+            pub fn value(&self) -> $result_type {
+                self.fletcher().value()
+            }
+        }
+
+        impl Default for BufferedFletcher<$result_type> {
+            fn default() -> Self {
+                Self {
+                    fletcher: Fletcher::default(),
+                    pending: [0; MAX_VEC_SIZE],
+                    pending_len: 0,
+                }
+            }
+        }
+    };
+}
+
+impl_buffered_fletcher!(u16, u8, 1);
+impl_buffered_fletcher!(u32, u16, 2);
+impl_buffered_fletcher!(u64, u32, 4);
+impl_buffered_fletcher!(u128, u64, 8);
@@ -0,0 +1,210 @@
+//! A builder for checksums configured beyond the defaults of [`Fletcher::new`].
+
+use {
+    crate::{Endianness, Fletcher, FletcherChecksum, ModularFletcher},
+    core::convert::TryFrom,
+};
+
+/// Builder that collects the options of a checksum in one place and produces a
+/// [`ConfiguredFletcher`].
+///
+/// The options are:
+///
+/// * [`seed`](Self::seed): the initial `a` and `b` sums, zero by default.
+/// * [`endianness`](Self::endianness): the byte order used to group bytes into blocks,
+///   [`Endianness::DEFAULT`] by default.
+/// * [`modular`](Self::modular): reduce by `2^k - 1` as [`ModularFletcher`] does, instead of
+///   wrapping modulo `2^k`.
+/// * [`reflected`](Self::reflected): reverse the bits of every block before summing it, as
+///   [`Fletcher::update_with_slice_reflected`] does.
+///
+/// # Examples
+///
+/// ```
+/// use fletcher_simd::{Endianness, FletcherBuilder, ModularFletcher};
+///
+/// let mut configured = FletcherBuilder::new()
+///     .seed(1, 0)
+///     .endianness(Endianness::Big)
+///     .modular()
+///     .build::<u32>();
+/// configured.update_with_bytes(&[0x00, 0x01, 0x00, 0x02]);
+///
+/// let mut modular = ModularFletcher::<u32>::with_initial_values(1, 0);
+/// modular.update_with_slice(&[0x0001, 0x0002]);
+///
+/// assert_eq!(configured.value(), modular.value());
+/// ```
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct FletcherBuilder {
+    seed: (u128, u128),
+    endianness: Endianness,
+    modular: bool,
+    reflected: bool,
+}
+
+impl FletcherBuilder {
+    /// Constructs a new `FletcherBuilder` with the default options, which build a checksum that
+    /// is the same as [`Fletcher::new`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the initial `a` and `b` sums.
+    ///
+    /// The sums are checked against the block type when the checksum is built.
+    pub fn seed(mut self, a: u128, b: u128) -> Self {
+        self.seed = (a, b);
+        self
+    }
+
+    /// Sets the byte order used to group bytes into blocks in
+    /// [`update_with_bytes`](ConfiguredFletcher::update_with_bytes).
+    pub fn endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    /// Reduces both sums modulo `2^k - 1`, as [`ModularFletcher`] does.
+    pub fn modular(mut self) -> Self {
+        self.modular = true;
+        self
+    }
+
+    /// Reverses the bits of every block before it is summed.
+    pub fn reflected(mut self) -> Self {
+        self.reflected = true;
+        self
+    }
+
+    /// Builds a checksum of type `T` with the collected options.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either sum of the seed does not fit in `T::BlockType`.
+    pub fn build<T>(&self) -> ConfiguredFletcher<T>
+    where
+        T: BuildableChecksum,
+        T::BlockType: TryFrom<u128>,
+    {
+        let to_block = |sum: u128| {
+            T::BlockType::try_from(sum)
+                .ok()
+                .expect("the seed must fit in the block type")
+        };
+        let (a, b) = (to_block(self.seed.0), to_block(self.seed.1));
+
+        ConfiguredFletcher {
+            sums: if self.modular {
+                ConfiguredSums::Modular(ModularFletcher::with_initial_values(a, b))
+            } else {
+                ConfiguredSums::Wrapping(Fletcher::<T>::with_initial_values(a, b))
+            },
+            endianness: self.endianness,
+            reflected: self.reflected,
+        }
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// The checksums that [`FletcherBuilder::build`] can build, which are the widths up to 128 bits.
+/// There is no modular variant of [`Fletcher256`](crate::Fletcher256), so it is not one of them.
+///
+/// This trait is sealed, so it cannot be implemented outside of this crate.
+///
+/// ```compile_fail
+/// use fletcher_simd::{FletcherBuilder, U256};
+///
+/// let configured = FletcherBuilder::new().build::<U256>();
+/// ```
+pub trait BuildableChecksum: FletcherChecksum + sealed::Sealed {}
+
+/// A Fletcher checksum object built by [`FletcherBuilder`], which applies its options to every
+/// update.
+///
+/// This is implemented for the widths up to 128 bits, which all have a modular variant.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ConfiguredFletcher<T: FletcherChecksum> {
+    sums: ConfiguredSums<T>,
+    endianness: Endianness,
+    reflected: bool,
+}
+
+/// The sums of a [`ConfiguredFletcher`], in the variant it was built with.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum ConfiguredSums<T: FletcherChecksum> {
+    Wrapping(Fletcher<T>),
+    Modular(ModularFletcher<T>),
+}
+
+macro_rules! impl_configured_fletcher {
+    ($result_type:ty, $block_type:ty, $block_size:literal) => {
+        impl sealed::Sealed for $result_type {}
+        impl BuildableChecksum for $result_type {}
+
+        impl ConfiguredFletcher<$result_type> {
+            /// Updates the checksum with a slice of data of type `T::BlockType`.
+            pub fn update_with_slice(&mut self, data: &[$block_type]) {
+                match (&mut self.sums, self.reflected) {
+                    (ConfiguredSums::Wrapping(fletcher), false) => fletcher.update_with_slice(data),
+                    (ConfiguredSums::Wrapping(fletcher), true) => {
+                        fletcher.update_with_slice_reflected(data)
+                    }
+                    (ConfiguredSums::Modular(fletcher), false) => fletcher.update_with_slice(data),
+                    (ConfiguredSums::Modular(fletcher), true) => fletcher
+                        .update_with_iter_scalar(data.iter().map(|elem| elem.reverse_bits())),
+                }
+            }
+
+            /// Updates the checksum with a slice of bytes, grouped into blocks in the configured
+            /// byte order. If the length of `bytes` is not a multiple of the block size, the
+            /// trailing partial block is zero-padded.
+            pub fn update_with_bytes(&mut self, bytes: &[u8]) {
+                let endianness = self.endianness;
+                let reflected = self.reflected;
+
+                if let (ConfiguredSums::Wrapping(fletcher), false) = (&mut self.sums, reflected) {
+                    fletcher.update_with_bytes(bytes, endianness);
+                    return;
+                }
+
+                let blocks = bytes.chunks($block_size).map(|chunk| {
+                    let mut block = [0u8; $block_size];
+                    block[..chunk.len()].copy_from_slice(chunk);
+
+                    let block = match endianness {
+                        Endianness::Little => <$block_type>::from_le_bytes(block),
+                        Endianness::Big => <$block_type>::from_be_bytes(block),
+                    };
+                    if reflected {
+                        block.reverse_bits()
+                    } else {
+                        block
+                    }
+                });
+
+                match &mut self.sums {
+                    ConfiguredSums::Wrapping(fletcher) => fletcher.update_with_iter(blocks),
+                    ConfiguredSums::Modular(fletcher) => fletcher.update_with_iter_scalar(blocks),
+                }
+            }
+
+            /// Returns the checksum value.
+            #[must_use]
+            pub fn value(&self) -> $result_type {
+                match &self.sums {
+                    ConfiguredSums::Wrapping(fletcher) => fletcher.value(),
+                    ConfiguredSums::Modular(fletcher) => fletcher.value(),
+                }
+            }
+        }
+    };
+}
+
+impl_configured_fletcher!(u16, u8, 1);
+impl_configured_fletcher!(u32, u16, 2);
+impl_configured_fletcher!(u64, u32, 4);
+impl_configured_fletcher!(u128, u64, 8);
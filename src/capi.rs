@@ -0,0 +1,86 @@
+//! `extern "C"` functions for using the checksums from C and C++.
+//!
+//! Each width has a `new`, `update` and `value` function operating on the [`Fletcher`] state,
+//! which C code can declare as a struct of two integers of the block type, `a` then `b`:
+//!
+//! ```c
+//! typedef struct { uint16_t a, b; } fletcher_simd_fletcher32;
+//!
+//! fletcher_simd_fletcher32 fletcher_simd_fletcher32_new(void);
+//! void fletcher_simd_fletcher32_update(fletcher_simd_fletcher32 *state, const uint16_t *data,
+//!                                      size_t len);
+//! uint32_t fletcher_simd_fletcher32_value(const fletcher_simd_fletcher32 *state);
+//! ```
+//!
+//! There are no functions for `Fletcher128` or `Fletcher256`, since 128-bit integers do not have
+//! a stable C ABI.
+
+use crate::{Fletcher16, Fletcher32, Fletcher64};
+
+macro_rules! impl_capi {
+    ($new:ident, $update:ident, $value:ident, $fletcher:ty, $result_type:ty, $block_type:ty) => {
+        /// Returns a new checksum state with the default values.
+        #[no_mangle]
+        pub extern "C" fn $new() -> $fletcher {
+            <$fletcher>::new()
+        }
+
+        /// Updates the checksum state with `len` blocks starting at `data`.
+        ///
+        /// # Safety
+        ///
+        /// `state` must point to a valid checksum state. Unless `len` is 0, `data` must point to
+        /// `len` initialized blocks that do not overlap `state`.
+        #[no_mangle]
+        pub unsafe extern "C" fn $update(
+            state: *mut $fletcher,
+            data: *const $block_type,
+            len: usize,
+        ) {
+            if len == 0 {
+                return;
+            }
+
+            // SAFETY: The caller guarantees that both pointers are valid.
+            unsafe {
+                (*state).update_with_slice(core::slice::from_raw_parts(data, len));
+            }
+        }
+
+        /// Returns the value of the checksum state.
+        ///
+        /// # Safety
+        ///
+        /// `state` must point to a valid checksum state.
+        #[no_mangle]
+        pub unsafe extern "C" fn $value(state: *const $fletcher) -> $result_type {
+            // SAFETY: The caller guarantees that `state` is valid.
+            unsafe { (*state).value() }
+        }
+    };
+}
+
+impl_capi!(
+    fletcher_simd_fletcher16_new,
+    fletcher_simd_fletcher16_update,
+    fletcher_simd_fletcher16_value,
+    Fletcher16,
+    u16,
+    u8
+);
+impl_capi!(
+    fletcher_simd_fletcher32_new,
+    fletcher_simd_fletcher32_update,
+    fletcher_simd_fletcher32_value,
+    Fletcher32,
+    u32,
+    u16
+);
+impl_capi!(
+    fletcher_simd_fletcher64_new,
+    fletcher_simd_fletcher64_update,
+    fletcher_simd_fletcher64_value,
+    Fletcher64,
+    u64,
+    u32
+);
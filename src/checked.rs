@@ -0,0 +1,89 @@
+//! Fletcher checksums that report when the sums wrap.
+
+use {
+    crate::{Fletcher, FletcherChecksum},
+    num::traits::{CheckedAdd, WrappingAdd},
+};
+
+/// A Fletcher checksum object that detects when either sum exceeds the range of the block type.
+///
+/// [`Fletcher`] lets both sums wrap silently, which is part of its definition, but it also means
+/// that the checksum no longer grows with the input once they do. This keeps the same wrapping
+/// sums, so [`value`](Self::value) is identical to that of [`Fletcher`], and additionally records
+/// whether any addition wrapped, queryable with [`overflowed`](Self::overflowed).
+///
+/// Every addition is checked, so this always sums one block at a time. It is about as fast as
+/// [`Fletcher::update_with_iter_scalar`], which is up to several times slower than the SIMD
+/// implementation behind [`Fletcher::update_with_slice`], most of all for narrow blocks.
+///
+/// # Examples
+///
+/// ```
+/// use fletcher_simd::{CheckedFletcher, Fletcher16};
+///
+/// let mut checked = CheckedFletcher::<u16>::new();
+/// checked.update_with_slice(b"a");
+/// assert!(!checked.overflowed());
+///
+/// checked.update_with_slice(b"bcdefgh");
+/// assert!(checked.overflowed());
+/// assert_eq!(checked.value(), Fletcher16::from(&b"abcdefgh"[..]).value());
+/// ```
+#[derive(Copy, Clone, Default, Debug, PartialEq)]
+pub struct CheckedFletcher<T: FletcherChecksum> {
+    fletcher: Fletcher<T>,
+    overflowed: bool,
+}
+
+impl<T> CheckedFletcher<T>
+where
+    T: FletcherChecksum,
+    T::BlockType: CheckedAdd,
+{
+    /// Constructs a new `CheckedFletcher<T>` with the default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the checksum with a slice of data of type `T::BlockType`.
+    pub fn update_with_slice(&mut self, data: &[T::BlockType]) {
+        self.update_with_iter(data.iter().copied());
+    }
+
+    /// Updates the checksum with an iterator over elements of type `T::BlockType`.
+    pub fn update_with_iter<Iter>(&mut self, elems: Iter)
+    where
+        Iter: Iterator<Item = T::BlockType>,
+    {
+        let (mut a, mut b) = self.fletcher.components();
+
+        for elem in elems {
+            a = a.checked_add(&elem).unwrap_or_else(|| {
+                self.overflowed = true;
+                a.wrapping_add(&elem)
+            });
+            b = b.checked_add(&a).unwrap_or_else(|| {
+                self.overflowed = true;
+                b.wrapping_add(&a)
+            });
+        }
+
+        self.fletcher = Fletcher::<T>::with_initial_values(a, b);
+    }
+
+    /// Returns whether either sum has wrapped since the checksum was constructed.
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+
+    /// Returns the underlying wrapping checksum.
+    pub fn fletcher(&self) -> Fletcher<T> {
+        self.fletcher
+    }
+
+    /// Returns the checksum value, which is the same whether or not the sums have wrapped.
+    #[must_use]
+    pub fn value(&self) -> T {
+        self.fletcher.value()
+    }
+}
@@ -0,0 +1,62 @@
+//! A trait over the checksum widths, for code that is generic over the width it is given.
+
+use crate::{Fletcher, FletcherChecksum};
+
+/// Common interface of the checksums up to 128 bits wide, so that code can be generic over the
+/// width chosen by its caller.
+///
+/// [`Fletcher`] implements this for every width whose value fits in a `u128`, which excludes
+/// [`Fletcher256`](crate::Fletcher256). The inherent methods of the same names remain, and are
+/// the ones called on a concrete type.
+///
+/// # Examples
+///
+/// ```
+/// use fletcher_simd::{Checksum, Fletcher16, Fletcher64};
+///
+/// fn checksum_of<C: Checksum + Default>(data: &[C::Block]) -> u128 {
+///     let mut checksum = C::default();
+///     checksum.update_with_slice(data);
+///     checksum.value()
+/// }
+///
+/// assert_eq!(checksum_of::<Fletcher16>(b"abcde"), 0xC3EF);
+/// assert_eq!(checksum_of::<Fletcher64>(&[1, 2]), 0x0000_0004_0000_0003);
+/// ```
+pub trait Checksum {
+    /// The type of the blocks the checksum is computed over.
+    type Block;
+
+    /// Size of a block in bytes.
+    const BLOCK_BYTES: usize;
+
+    /// Updates the checksum with a slice of blocks.
+    fn update_with_slice(&mut self, data: &[Self::Block]);
+
+    /// Returns the checksum value, zero-extended to a `u128`.
+    fn value(&self) -> u128;
+
+    /// Resets the checksum to the default values, as if it had just been constructed.
+    fn reset(&mut self);
+}
+
+impl<T> Checksum for Fletcher<T>
+where
+    T: FletcherChecksum + Into<u128>,
+{
+    type Block = T::BlockType;
+
+    const BLOCK_BYTES: usize = T::BLOCK_BYTES;
+
+    fn update_with_slice(&mut self, data: &[T::BlockType]) {
+        Fletcher::<T>::update_with_slice(self, data);
+    }
+
+    fn value(&self) -> u128 {
+        Fletcher::<T>::value(self).into()
+    }
+
+    fn reset(&mut self) {
+        Fletcher::<T>::reset(self);
+    }
+}
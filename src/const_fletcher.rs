@@ -0,0 +1,81 @@
+//! Scalar checksums of byte slices that can be evaluated in `const` context.
+
+use crate::{Endianness, U256};
+
+macro_rules! impl_const_fletcher {
+    (
+        $name:ident,
+        $result_type:ty,
+        $block_type:ty,
+        $alias:literal,
+        $result_path:literal,
+        |$a:ident, $b:ident| $value:expr
+    ) => {
+        #[doc = concat!("Computes the ", $alias, " checksum of bytes in `const` context.")]
+        ///
+        /// Bytes are read as blocks in the byte order [`Endianness::DEFAULT`] and a trailing
+        /// partial block is zero-padded, as with
+        /// [`update_with_bytes`](crate::Fletcher::update_with_bytes). This always uses the
+        /// scalar implementation, so prefer the checksum objects for data only known at runtime.
+        ///
+        /// ```
+        #[doc = concat!("use fletcher_simd::{", stringify!($name), ", Endianness, ", $alias, "};")]
+        ///
+        #[doc = concat!("const EXPECTED: ", $result_path, " = ", stringify!($name), "(b\"abcdefgh\");")]
+        ///
+        #[doc = concat!("let mut fletcher = ", $alias, "::new();")]
+        /// fletcher.update_with_bytes(b"abcdefgh", Endianness::DEFAULT);
+        /// assert_eq!(fletcher.value(), EXPECTED);
+        /// ```
+        pub const fn $name(data: &[u8]) -> $result_type {
+            const BLOCK_BYTES: usize = core::mem::size_of::<$block_type>();
+            const BIG_ENDIAN: bool = matches!(Endianness::DEFAULT, Endianness::Big);
+
+            let mut $a: $block_type = 0;
+            let mut $b: $block_type = 0;
+
+            let mut start = 0;
+            while start < data.len() {
+                let mut block: $block_type = 0;
+                let mut i = 0;
+                while i < BLOCK_BYTES && start + i < data.len() {
+                    let shift = if BIG_ENDIAN { BLOCK_BYTES - 1 - i } else { i };
+                    block |= (data[start + i] as $block_type) << (shift * 8);
+                    i += 1;
+                }
+
+                $a = $a.wrapping_add(block);
+                $b = $b.wrapping_add($a);
+                start += BLOCK_BYTES;
+            }
+
+            $value
+        }
+    };
+}
+
+impl_const_fletcher!(const_fletcher16, u16, u8, "Fletcher16", "u16", |a, b| {
+    ((b as u16) << 8) | a as u16
+});
+impl_const_fletcher!(const_fletcher32, u32, u16, "Fletcher32", "u32", |a, b| {
+    ((b as u32) << 16) | a as u32
+});
+impl_const_fletcher!(const_fletcher64, u64, u32, "Fletcher64", "u64", |a, b| {
+    ((b as u64) << 32) | a as u64
+});
+impl_const_fletcher!(
+    const_fletcher128,
+    u128,
+    u64,
+    "Fletcher128",
+    "u128",
+    |a, b| ((b as u128) << 64) | a as u128
+);
+impl_const_fletcher!(
+    const_fletcher256,
+    U256,
+    u128,
+    "Fletcher256",
+    "fletcher_simd::U256",
+    |a, b| U256::from_words(b, a)
+);
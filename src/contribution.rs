@@ -0,0 +1,47 @@
+//! What a slice adds to the sums of a checksum, independent of the sums before it.
+
+use crate::{Fletcher, U256};
+
+macro_rules! impl_contribution {
+    ($name:ident, $result_type:ty, $block_type:ty, $alias:literal) => {
+        #[doc = concat!("Returns what `data` adds to the sums of a ", $alias, " checksum, as `(a_delta, b_delta, len)`.")]
+        ///
+        /// The deltas are the sums over `data` starting from zero, and `len` is the number of
+        /// blocks in it. Updating a checksum with sums `(a, b)` with `data` leaves it with
+        /// `a + a_delta` and `b + b_delta + len * a`, all wrapping, which is the same fold
+        /// [`combine`](crate::Fletcher::combine) does. This is useful for combining sums kept
+        /// outside of a [`Fletcher`], or for inspecting what a piece of the input contributed.
+        ///
+        /// ```
+        #[doc = concat!("use fletcher_simd::{", stringify!($name), ", ", $alias, "};")]
+        ///
+        /// let first = [1, 2, 3];
+        /// let second = [4, 5, 6, 7];
+        ///
+        #[doc = concat!("let mut fletcher = ", $alias, "::new();")]
+        /// fletcher.update_with_slice(&first);
+        /// let (a, b) = fletcher.components();
+        ///
+        #[doc = concat!("let (a_delta, b_delta, len) = ", stringify!($name), "(&second);")]
+        #[doc = concat!("let folded = ", $alias, "::with_initial_values(")]
+        ///     a.wrapping_add(a_delta),
+        ///     b.wrapping_add(b_delta).wrapping_add(a.wrapping_mul(len as _)),
+        /// );
+        ///
+        /// fletcher.update_with_slice(&second);
+        /// assert_eq!(folded, fletcher);
+        /// ```
+        pub fn $name(data: &[$block_type]) -> ($block_type, $block_type, usize) {
+            let mut fletcher = Fletcher::<$result_type>::new();
+            fletcher.update_with_slice(data);
+            let (a_delta, b_delta) = fletcher.components();
+            (a_delta, b_delta, data.len())
+        }
+    };
+}
+
+impl_contribution!(fletcher16_contribution, u16, u8, "Fletcher16");
+impl_contribution!(fletcher32_contribution, u32, u16, "Fletcher32");
+impl_contribution!(fletcher64_contribution, u64, u32, "Fletcher64");
+impl_contribution!(fletcher128_contribution, u128, u64, "Fletcher128");
+impl_contribution!(fletcher256_contribution, U256, u128, "Fletcher256");
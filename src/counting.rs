@@ -0,0 +1,310 @@
+//! Fletcher checksums that also count the blocks they have been fed.
+
+use {
+    crate::{Endianness, Fletcher, FletcherChecksum, U256},
+    core::{
+        ops::{Add, AddAssign},
+        simd::{LaneCount, SupportedLaneCount},
+    },
+};
+
+/// A Fletcher checksum object that tracks the number of blocks it has been updated with.
+///
+/// [`Fletcher`] itself only holds its two sums, so that its layout stays that of the checksum
+/// state. The methods that need the input length, such as [`Fletcher::combine`] and
+/// [`Fletcher::value_with_length`], take it as an argument. This supplies it instead.
+///
+/// The update methods here forward to those of [`Fletcher`] and count the blocks they checksum.
+/// The byte inputs, including [`update_with_reader`](Self::update_with_reader), count the bytes
+/// rounded up to whole blocks, as a trailing partial block is zero-padded to one. Other methods
+/// of [`Fletcher`] are not available, since they could not keep the length in step.
+///
+/// The length is a `u64` that wraps on overflow rather than saturating, like the sums do.
+/// Combining only uses the length modulo `2^k` for a `k`-bit `BlockType`, as the sums wrap modulo
+/// `2^k`. For blocks of up to 64 bits, the length modulo `2^64` determines that, so a wrapped
+/// length still combines correctly. [`U256`] has 128-bit blocks, so it would need the length
+/// modulo `2^128`, and a wrapped length would combine incorrectly. Either way, wrapping takes
+/// `2^64` blocks, which is not reachable in practice.
+///
+/// # Examples
+///
+/// ```
+/// use fletcher_simd::{CountingFletcher, Fletcher16};
+///
+/// let mut first = CountingFletcher::<u16>::new();
+/// first.update_with_slice(b"abc");
+///
+/// let mut second = CountingFletcher::<u16>::new();
+/// second.update_with_slice(b"defgh");
+///
+/// first.combine(&second);
+/// assert_eq!(first.len(), 8);
+/// assert_eq!(first.value(), Fletcher16::from(&b"abcdefgh"[..]).value());
+/// ```
+#[derive(Copy, Clone, Default, Debug, PartialEq)]
+pub struct CountingFletcher<T: FletcherChecksum> {
+    fletcher: Fletcher<T>,
+    len: u64,
+}
+
+macro_rules! impl_counting_fletcher {
+    ($result_type:ty, $block_type:ty) => {
+        impl CountingFletcher<$result_type> {
+            /// Constructs a new `CountingFletcher<T>` with the default values and no blocks.
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// Updates the checksum with a slice of data of type `T::BlockType`.
+            pub fn update_with_slice(&mut self, data: &[$block_type]) {
+                self.fletcher.update_with_slice(data);
+                self.len = self.len.wrapping_add(data.len() as u64);
+            }
+
+            /// Updates the checksum with an iterator over elements of type `T::BlockType`.
+            pub fn update_with_iter<Iter>(&mut self, elems: Iter)
+            where
+                Iter: Iterator<Item = $block_type>,
+            {
+                let len = &mut self.len;
+                self.fletcher
+                    .update_with_iter(elems.inspect(|_| *len = len.wrapping_add(1)));
+            }
+
+            /// Updates the checksum with an iterator over elements of type `T::BlockType` using
+            /// a scalar-only implementation.
+            pub fn update_with_iter_scalar<Iter>(&mut self, elems: Iter)
+            where
+                Iter: Iterator<Item = $block_type>,
+            {
+                let len = &mut self.len;
+                self.fletcher
+                    .update_with_iter_scalar(elems.inspect(|_| *len = len.wrapping_add(1)));
+            }
+
+            /// Updates the checksum with `count` copies of `elem`. See
+            /// [`Fletcher::update_repeated`].
+            pub fn update_repeated(&mut self, elem: $block_type, count: usize) {
+                self.fletcher.update_repeated(elem, count);
+                self.len = self.len.wrapping_add(count as u64);
+            }
+
+            /// Updates the checksum with a slice of bytes in the given byte order. See
+            /// [`Fletcher::update_with_bytes`].
+            ///
+            /// A trailing partial block counts as a whole block, since it is checksummed as one.
+            pub fn update_with_bytes(&mut self, bytes: &[u8], endianness: Endianness) {
+                self.fletcher.update_with_bytes(bytes, endianness);
+                self.add_bytes(bytes.len() as u64);
+            }
+
+            /// Updates the checksum with a slice of little-endian bytes. See
+            /// [`update_with_bytes`](Self::update_with_bytes).
+            pub fn update_with_slice_le(&mut self, bytes: &[u8]) {
+                self.update_with_bytes(bytes, Endianness::Little);
+            }
+
+            /// Updates the checksum with a slice of big-endian bytes. See
+            /// [`update_with_bytes`](Self::update_with_bytes).
+            pub fn update_with_slice_be(&mut self, bytes: &[u8]) {
+                self.update_with_bytes(bytes, Endianness::Big);
+            }
+
+            /// Updates the checksum with every byte read from `reader`. See
+            /// [`Fletcher::update_with_reader`].
+            ///
+            /// If an error is returned, the length counts the whole blocks that were checksummed
+            /// before it.
+            pub fn update_with_reader<R: std::io::Read>(
+                &mut self,
+                reader: R,
+                endianness: Endianness,
+            ) -> std::io::Result<()> {
+                let mut bytes = 0;
+                let result = self.fletcher.update_with_reader(
+                    CountBytes {
+                        inner: reader,
+                        bytes: &mut bytes,
+                    },
+                    endianness,
+                );
+                self.add_read_bytes(bytes, result.is_ok());
+                result
+            }
+
+            /// Updates the checksum with every byte of `reader` and returns the number of bytes
+            /// consumed. See [`Fletcher::update_with_bufread`].
+            ///
+            /// If an error is returned, the length counts the whole blocks that were checksummed
+            /// before it.
+            pub fn update_with_bufread<R: std::io::BufRead>(
+                &mut self,
+                reader: &mut R,
+                endianness: Endianness,
+            ) -> std::io::Result<u64> {
+                let mut bytes = 0;
+                let result = self.fletcher.update_with_bufread(
+                    &mut CountBytes {
+                        inner: reader,
+                        bytes: &mut bytes,
+                    },
+                    endianness,
+                );
+                self.add_read_bytes(bytes, result.is_ok());
+                result
+            }
+
+            /// Adds the number of blocks spanned by `bytes` bytes, counting a trailing partial
+            /// block.
+            fn add_bytes(&mut self, bytes: u64) {
+                let block_bytes = <$result_type as FletcherChecksum>::BLOCK_BYTES as u64;
+                self.len = self
+                    .len
+                    .wrapping_add(bytes / block_bytes + (bytes % block_bytes != 0) as u64);
+            }
+
+            /// Adds the blocks of `bytes` bytes read by `update_with_reader` or
+            /// `update_with_bufread`. A trailing partial block is only checksummed once the input
+            /// ends, so it is not counted if reading failed.
+            fn add_read_bytes(&mut self, bytes: u64, finished: bool) {
+                if finished {
+                    self.add_bytes(bytes);
+                } else {
+                    let block_bytes = <$result_type as FletcherChecksum>::BLOCK_BYTES as u64;
+                    self.add_bytes(bytes - bytes % block_bytes);
+                }
+            }
+
+            /// Combines the checksum with the checksum of data that follows it, using the length
+            /// `other` has tracked. See [`Fletcher::combine`].
+            pub fn combine(&mut self, other: &Self) {
+                // Unlike `Fletcher::combine`, this does not first truncate the length to a `usize`.
+                self.fletcher
+                    .combine_reduced(&other.fletcher, other.len as $block_type);
+                self.len = self.len.wrapping_add(other.len);
+            }
+
+            /// Returns the number of blocks the checksum has been updated with.
+            pub fn len(&self) -> u64 {
+                self.len
+            }
+
+            /// Returns whether the checksum has not been updated with any blocks.
+            pub fn is_empty(&self) -> bool {
+                self.len == 0
+            }
+
+            /// Returns the underlying checksum, without the length.
+            pub fn fletcher(&self) -> Fletcher<$result_type> {
+                self.fletcher
+            }
+
+            /// Returns the checksum value.
+            #[must_use]
+            pub fn value(&self) -> $result_type {
+                self.fletcher.value()
+            }
+
+            /// Returns the checksum value with the tracked length mixed in. See
+            /// [`Fletcher::value_with_length`].
+            #[must_use]
+            pub fn value_with_length(&self) -> $result_type {
+                self.fletcher.value_with_length_u64(self.len)
+            }
+        }
+
+        /// Concatenates two checksums, as with [`combine`](CountingFletcher::combine).
+        ///
+        /// Unlike a plain [`Fletcher`], a `CountingFletcher` knows the length of the data that
+        /// follows, so no length has to be supplied.
+        ///
+        /// ```
+        /// use fletcher_simd::CountingFletcher;
+        ///
+        /// let mut first = CountingFletcher::<u16>::new();
+        /// first.update_with_slice(b"abc");
+        ///
+        /// let mut second = CountingFletcher::<u16>::new();
+        /// second.update_with_slice(b"defgh");
+        ///
+        /// let whole = first + second;
+        /// assert_eq!(whole.value(), 0xF824);
+        /// ```
+        impl Add for CountingFletcher<$result_type> {
+            type Output = Self;
+
+            fn add(mut self, other: Self) -> Self {
+                self.combine(&other);
+                self
+            }
+        }
+
+        impl AddAssign for CountingFletcher<$result_type> {
+            fn add_assign(&mut self, other: Self) {
+                self.combine(&other);
+            }
+        }
+    };
+}
+
+/// Adds the update methods that only exist for the widths with SIMD vectors of their blocks.
+macro_rules! impl_counting_fletcher_simd {
+    ($result_type:ty, $block_type:ty, $signed_type:ty) => {
+        impl CountingFletcher<$result_type> {
+            /// Updates the checksum with a slice of data of type `T::BlockType`, in SIMD vectors of
+            /// `LANES` lanes. See [`Fletcher::update_with_slice_lanes`].
+            pub fn update_with_slice_lanes<const LANES: usize>(&mut self, data: &[$block_type])
+            where
+                LaneCount<LANES>: SupportedLaneCount,
+            {
+                self.fletcher.update_with_slice_lanes::<LANES>(data);
+                self.len = self.len.wrapping_add(data.len() as u64);
+            }
+
+            /// Updates the checksum with a slice of signed blocks. See
+            /// [`Fletcher::update_with_slice_signed`].
+            pub fn update_with_slice_signed(&mut self, data: &[$signed_type]) {
+                self.fletcher.update_with_slice_signed(data);
+                self.len = self.len.wrapping_add(data.len() as u64);
+            }
+        }
+    };
+}
+
+/// Counts the bytes that a reader hands out, through [`Read::read`](std::io::Read::read) or
+/// [`BufRead::consume`](std::io::BufRead::consume). `Fletcher` only uses one of the two on a
+/// given reader, so no byte is counted twice.
+struct CountBytes<'a, R> {
+    inner: R,
+    bytes: &'a mut u64,
+}
+
+impl<R: std::io::Read> std::io::Read for CountBytes<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        *self.bytes += read as u64;
+        Ok(read)
+    }
+}
+
+impl<R: std::io::BufRead> std::io::BufRead for CountBytes<'_, R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        *self.bytes += amt as u64;
+        self.inner.consume(amt);
+    }
+}
+
+impl_counting_fletcher!(u16, u8);
+impl_counting_fletcher!(u32, u16);
+impl_counting_fletcher!(u64, u32);
+impl_counting_fletcher!(u128, u64);
+impl_counting_fletcher!(U256, u128);
+
+impl_counting_fletcher_simd!(u16, u8, i8);
+impl_counting_fletcher_simd!(u32, u16, i16);
+impl_counting_fletcher_simd!(u64, u32, i32);
+impl_counting_fletcher_simd!(u128, u64, i64);
@@ -0,0 +1,119 @@
+//! Decoding of a vector of bytes into a vector of blocks without leaving the registers, for
+//! [`Fletcher::update_with_bytes`](crate::Fletcher::update_with_bytes).
+//!
+//! The bytes are loaded as one `Simd<u8, MAX_VEC_SIZE>`. Big-endian input is first reversed within
+//! each block by a constant swizzle, i.e. an in-register byte swap. Blocks are then assembled from
+//! the even and odd lanes of the little-endian decoding at half the width, down to bytes.
+
+use {
+    crate::{Endianness, MAX_VEC_SIZE},
+    core::{
+        ops::{BitOr, Shl},
+        simd::{LaneCount, Simd, SimdCast, SimdElement, SupportedLaneCount, Swizzle},
+    },
+};
+
+/// Returns the indices `start, start + 2, start + 4, ...`.
+const fn every_other_index<const LANES: usize>(start: usize) -> [usize; LANES] {
+    let mut index = [0; LANES];
+    let mut lane = 0;
+    while lane < LANES {
+        index[lane] = 2 * lane + start;
+        lane += 1;
+    }
+    index
+}
+
+/// Returns the indices that reverse the bytes within each block of `block_size` bytes.
+const fn swap_bytes_index<const LEN: usize>(block_size: usize) -> [usize; LEN] {
+    let mut index = [0; LEN];
+    let mut i = 0;
+    while i < LEN {
+        index[i] = i - i % block_size + (block_size - 1 - i % block_size);
+        i += 1;
+    }
+    index
+}
+
+/// Swizzle selecting the even lanes, which are the low halves of the blocks twice as wide.
+struct Even;
+
+impl<const INPUT_LANES: usize, const OUTPUT_LANES: usize> Swizzle<INPUT_LANES, OUTPUT_LANES>
+    for Even
+{
+    const INDEX: [usize; OUTPUT_LANES] = every_other_index(0);
+}
+
+/// Swizzle selecting the odd lanes, which are the high halves of the blocks twice as wide.
+struct Odd;
+
+impl<const INPUT_LANES: usize, const OUTPUT_LANES: usize> Swizzle<INPUT_LANES, OUTPUT_LANES>
+    for Odd
+{
+    const INDEX: [usize; OUTPUT_LANES] = every_other_index(1);
+}
+
+/// Swizzle reversing the bytes of every block of `BLOCK_SIZE` bytes.
+struct SwapBytes<const BLOCK_SIZE: usize>;
+
+impl<const BLOCK_SIZE: usize> Swizzle<MAX_VEC_SIZE, MAX_VEC_SIZE> for SwapBytes<BLOCK_SIZE> {
+    const INDEX: [usize; MAX_VEC_SIZE] = swap_bytes_index(BLOCK_SIZE);
+}
+
+/// Joins the even and odd lanes of `halves` into the low and high halves of wider lanes.
+#[inline(always)]
+fn join_halves<Half, Wide, const HALF_LANES: usize, const LANES: usize>(
+    halves: Simd<Half, HALF_LANES>,
+    half_bits: Wide,
+) -> Simd<Wide, LANES>
+where
+    Half: SimdElement + SimdCast,
+    Wide: SimdElement + SimdCast,
+    LaneCount<HALF_LANES>: SupportedLaneCount,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<Wide, LANES>: BitOr<Output = Simd<Wide, LANES>> + Shl<Output = Simd<Wide, LANES>>,
+{
+    Even::swizzle(halves).cast::<Wide>()
+        | Odd::swizzle(halves).cast::<Wide>() << Simd::splat(half_bits)
+}
+
+/// Private helper trait for decoding a vector of blocks from the bytes it spans.
+pub(crate) trait FromBytesVec {
+    /// Groups `bytes` into blocks, the first byte of each being the least significant.
+    fn from_le_bytes_vec(bytes: Simd<u8, MAX_VEC_SIZE>) -> Self;
+
+    /// Groups `bytes` into blocks in the byte order `endianness`.
+    fn from_bytes_vec(bytes: Simd<u8, MAX_VEC_SIZE>, endianness: Endianness) -> Self;
+}
+
+macro_rules! impl_from_bytes_vec {
+    ($t:ty, $block_size:literal, |$bytes:ident| $join:expr) => {
+        impl FromBytesVec for Simd<$t, { MAX_VEC_SIZE / $block_size }> {
+            #[inline(always)]
+            fn from_le_bytes_vec($bytes: Simd<u8, MAX_VEC_SIZE>) -> Self {
+                $join
+            }
+
+            #[inline(always)]
+            fn from_bytes_vec(bytes: Simd<u8, MAX_VEC_SIZE>, endianness: Endianness) -> Self {
+                match endianness {
+                    Endianness::Little => Self::from_le_bytes_vec(bytes),
+                    Endianness::Big => {
+                        Self::from_le_bytes_vec(SwapBytes::<$block_size>::swizzle(bytes))
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_from_bytes_vec!(u8, 1, |bytes| bytes);
+impl_from_bytes_vec!(u16, 2, |bytes| join_halves(bytes, 8));
+impl_from_bytes_vec!(u32, 4, |bytes| join_halves(
+    Simd::<u16, { MAX_VEC_SIZE / 2 }>::from_le_bytes_vec(bytes),
+    16
+));
+impl_from_bytes_vec!(u64, 8, |bytes| join_halves(
+    Simd::<u32, { MAX_VEC_SIZE / 4 }>::from_le_bytes_vec(bytes),
+    32
+));
@@ -0,0 +1,59 @@
+//! Reporting which instruction set the SIMD implementation was dispatched to.
+
+/// Returns the target features of the SIMD implementation that checksums use on this CPU, as in
+/// the `multiversion` clone names: `"avx+avx2"`, `"avx"`, `"sse+sse2"`, `"sse"` or `"neon"`, or
+/// `"none"` for the default implementation.
+///
+/// With `runtime_dispatch`, this makes the same choice the dispatcher does, so it reports the
+/// first clone whose features are detected on the running CPU. Without it, it reports the
+/// features enabled at compile time. The number of lanes does not depend on this, see e.g.
+/// [`Fletcher16::LANES`](crate::Fletcher16::LANES).
+///
+/// ```
+/// let features = fletcher_simd::dispatched_target_features();
+/// println!("summing with {} lanes of {}", fletcher_simd::Fletcher16::LANES, features);
+/// ```
+pub fn dispatched_target_features() -> &'static str {
+    // In the same order as the clones of `update_fletcher_simd`, which are tried first to last.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if detected!(is_x86_feature_detected, "avx", "avx2") {
+            return "avx+avx2";
+        }
+        if detected!(is_x86_feature_detected, "avx") {
+            return "avx";
+        }
+        if detected!(is_x86_feature_detected, "sse", "sse2") {
+            return "sse+sse2";
+        }
+        if detected!(is_x86_feature_detected, "sse") {
+            return "sse";
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    if detected!(is_aarch64_feature_detected, "neon") {
+        return "neon";
+    }
+
+    #[cfg(target_arch = "arm")]
+    if detected!(is_arm_feature_detected, "neon") {
+        return "neon";
+    }
+
+    "none"
+}
+
+/// Whether all of the given target features are available, detected at runtime with
+/// `runtime_dispatch` and enabled at compile time otherwise.
+macro_rules! detected {
+    // The features are `tt`s, since the detection macros cannot match a captured `literal`.
+    ($detect:ident, $($feature:tt),+) => {{
+        #[cfg(feature = "runtime_dispatch")]
+        let detected = true $(&& std::arch::$detect!($feature))+;
+        #[cfg(not(feature = "runtime_dispatch"))]
+        let detected = true $(&& cfg!(target_feature = $feature))+;
+        detected
+    }};
+}
+use detected;
@@ -0,0 +1,82 @@
+//! Locating where two buffers of blocks first differ, e.g. after their checksums disagree.
+
+use {
+    crate::{Fletcher, MAX_VEC_SIZE, U256},
+    core::simd::{LaneCount, Mask, Simd, SimdElement, SimdPartialEq, SupportedLaneCount},
+};
+
+/// Function that returns the index of the first position at which `a` and `b` differ, comparing
+/// `LANES` blocks at a time. If one is a prefix of the other, that is the length of the shorter.
+fn first_divergence_lanes<T, const LANES: usize>(a: &[T], b: &[T]) -> Option<usize>
+where
+    T: SimdElement + PartialEq,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<T, LANES>: SimdPartialEq<Mask = Mask<T::Mask, LANES>>,
+{
+    let len = a.len().min(b.len());
+    let (a_prefix, b_prefix) = (&a[..len], &b[..len]);
+
+    let a_chunks = a_prefix.chunks_exact(LANES);
+    let b_chunks = b_prefix.chunks_exact(LANES);
+    let checked = len - a_chunks.remainder().len();
+
+    // Only find the lane once a vector is known to differ, which happens at most once.
+    for (index, (a_chunk, b_chunk)) in a_chunks.zip(b_chunks).enumerate() {
+        if Simd::<T, LANES>::from_slice(a_chunk)
+            .simd_ne(Simd::from_slice(b_chunk))
+            .any()
+        {
+            return first_divergence_scalar(a_chunk, b_chunk).map(|lane| index * LANES + lane);
+        }
+    }
+
+    first_divergence_scalar(&a_prefix[checked..], &b_prefix[checked..])
+        .map(|offset| checked + offset)
+        .or_else(|| (a.len() != b.len()).then_some(len))
+}
+
+/// Scalar version of [`first_divergence_lanes`] for slices of the same length.
+fn first_divergence_scalar<T: PartialEq>(a: &[T], b: &[T]) -> Option<usize> {
+    a.iter().zip(b).position(|(x, y)| x != y)
+}
+
+macro_rules! impl_first_divergence {
+    ($result_type:ty, $block_type:ty, $block_size:literal) => {
+        impl Fletcher<$result_type> {
+            /// Returns the index of the first block at which `a` and `b` differ, comparing a
+            /// whole SIMD vector of blocks at a time, or `None` if they are equal.
+            ///
+            /// If one slice is a prefix of the other, the first divergence is the length of the
+            /// shorter one. This is meant for locating the damage once two checksums that should
+            /// match do not.
+            ///
+            /// ```
+            /// use fletcher_simd::Fletcher16;
+            ///
+            /// assert_eq!(Fletcher16::first_divergence(b"abcdef", b"abcXef"), Some(3));
+            /// assert_eq!(Fletcher16::first_divergence(b"abc", b"abcdef"), Some(3));
+            /// assert_eq!(Fletcher16::first_divergence(b"abc", b"abc"), None);
+            /// ```
+            pub fn first_divergence(a: &[$block_type], b: &[$block_type]) -> Option<usize> {
+                first_divergence_lanes::<$block_type, { MAX_VEC_SIZE / $block_size }>(a, b)
+            }
+        }
+    };
+}
+
+impl_first_divergence!(u16, u8, 1);
+impl_first_divergence!(u32, u16, 2);
+impl_first_divergence!(u64, u32, 4);
+impl_first_divergence!(u128, u64, 8);
+
+impl Fletcher<U256> {
+    /// Returns the index of the first block at which `a` and `b` differ, or `None` if they are
+    /// equal.
+    ///
+    /// If one slice is a prefix of the other, the first divergence is the length of the shorter
+    /// one. There are no SIMD vectors of `u128`, so this compares one block at a time.
+    pub fn first_divergence(a: &[u128], b: &[u128]) -> Option<usize> {
+        first_divergence_scalar(a, b)
+            .or_else(|| (a.len() != b.len()).then_some(a.len().min(b.len())))
+    }
+}
@@ -0,0 +1,86 @@
+//! Experimental APIs that may change or be removed without a major version bump.
+
+use crate::{sum_widened, Fletcher32, MAX_VEC_SIZE};
+
+/// Maximum number of blocks that are summed before reducing.
+///
+/// Each run is summed from zero in `u32` lanes, so the run length `n` must satisfy
+/// `65535 * n * (n + 1) / 2 <= u32::MAX` for `b` to stay exact.
+const MAX_RUN_LEN: usize = 361;
+
+/// Number of `u32` lanes the blocks are widened into.
+const NUM_LANES: usize = MAX_VEC_SIZE / 4;
+
+/// A Fletcher-32 checksum object whose sums are reduced by a user-supplied function.
+///
+/// This is created by [`Fletcher32::with_reducer`]. Blocks are summed in widened `u32` lanes and
+/// the reducer is applied to every intermediate sum before it could overflow, which makes it
+/// possible to experiment with reduction strategies such as the classic modulus of `65535`.
+///
+/// The reducer must be congruent to some fixed modulus, i.e. `reducer(x)` must equal `x` modulo
+/// `m` for every `x`. Otherwise, the result depends on how the input was split into runs.
+///
+/// # Examples
+///
+/// ```
+/// use fletcher_simd::Fletcher32;
+///
+/// // "abcdefgh" read as little-endian 16-bit blocks.
+/// let data = [0x6261, 0x6463, 0x6665, 0x6867];
+///
+/// let mut fletcher = Fletcher32::with_reducer(|x| (x % 65535) as u16);
+/// fletcher.update_with_slice(&data);
+///
+/// assert_eq!(fletcher.value(), 0xEBE19591);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct ReducingFletcher32<R> {
+    a: u16,
+    b: u16,
+    reducer: R,
+}
+
+impl Fletcher32 {
+    /// Constructs a new checksum object whose sums are reduced by `reducer`.
+    ///
+    /// See [`ReducingFletcher32`] for the requirements on `reducer`.
+    pub fn with_reducer<R>(reducer: R) -> ReducingFletcher32<R>
+    where
+        R: Fn(u32) -> u16,
+    {
+        ReducingFletcher32 {
+            a: 0,
+            b: 0,
+            reducer,
+        }
+    }
+}
+
+impl<R> ReducingFletcher32<R>
+where
+    R: Fn(u32) -> u16,
+{
+    /// Updates the checksum with a slice of data of type `u16`.
+    pub fn update_with_slice(&mut self, data: &[u16]) {
+        let reduce = |x: u32| (self.reducer)(x) as u32;
+
+        let mut a = self.a as u32;
+        let mut b = self.b as u32;
+
+        for run in data.chunks(MAX_RUN_LEN) {
+            let (run_a, run_b) = sum_widened::<u16, u32, NUM_LANES>(run);
+
+            // Each term is reduced first so that the sum of three reduced values cannot overflow.
+            b = reduce(b + reduce(run_b) + reduce(run.len() as u32 * a));
+            a = reduce(a + reduce(run_a));
+        }
+
+        self.a = a as u16;
+        self.b = b as u16;
+    }
+
+    /// Returns the checksum value.
+    pub fn value(&self) -> u32 {
+        ((self.b as u32) << 16) | self.a as u32
+    }
+}
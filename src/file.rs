@@ -0,0 +1,28 @@
+//! One-shot checksums of files on disk.
+
+use {
+    crate::{Endianness, Fletcher, U256},
+    std::{fs::File, io, path::Path},
+};
+
+macro_rules! impl_file {
+    ($name:ident, $result_type:ty, $alias:literal) => {
+        #[doc = concat!("Computes the ", $alias, " checksum of the file at `path`.")]
+        ///
+        /// The file is streamed through
+        /// [`update_with_reader`](crate::Fletcher::update_with_reader) with blocks in the byte
+        /// order [`Endianness::DEFAULT`], so it is never read into memory all at once. A
+        /// trailing partial block is zero-padded.
+        pub fn $name(path: impl AsRef<Path>) -> io::Result<$result_type> {
+            let mut fletcher = Fletcher::<$result_type>::new();
+            fletcher.update_with_reader(File::open(path)?, Endianness::DEFAULT)?;
+            Ok(fletcher.value())
+        }
+    };
+}
+
+impl_file!(fletcher16_file, u16, "Fletcher16");
+impl_file!(fletcher32_file, u32, "Fletcher32");
+impl_file!(fletcher64_file, u64, "Fletcher64");
+impl_file!(fletcher128_file, u128, "Fletcher128");
+impl_file!(fletcher256_file, U256, "Fletcher256");
@@ -0,0 +1,80 @@
+//! [`Hasher`] implementations, so that checksums can be fed anything that implements [`Hash`].
+//!
+//! [`Hash`]: core::hash::Hash
+
+use {crate::Fletcher, core::hash::Hasher};
+
+macro_rules! impl_hasher {
+    (
+        $result_type:ty,
+        $block_type:ty,
+        [$($write_int:ident: $int_type:ty),+],
+        |$value:ident| $finish:expr
+    ) => {
+        /// Feeds written data into the checksum as blocks in [`Endianness::NATIVE`] byte order, so
+        /// the value depends on the endianness of the target.
+        ///
+        /// Each call to `write` is passed to [`update_bytes_as_blocks`], which zero-pads a
+        /// trailing partial block, so splitting a write in two can change the value unless the
+        /// split falls on a block boundary. Writes of an integer whose width is a multiple of the
+        /// block's are split into blocks arithmetically and summed directly, which is the same as
+        /// writing its native-endian bytes. `finish` returns the value, with the halves of a
+        /// 128-bit value XORed together.
+        ///
+        /// [`Endianness::NATIVE`]: crate::Endianness::NATIVE
+        /// [`update_bytes_as_blocks`]: Fletcher::update_bytes_as_blocks
+        impl Hasher for Fletcher<$result_type> {
+            fn write(&mut self, bytes: &[u8]) {
+                self.update_bytes_as_blocks(bytes);
+            }
+
+            $(
+                fn $write_int(&mut self, int: $int_type) {
+                    const NUM_BLOCKS: u32 = <$int_type>::BITS / <$block_type>::BITS;
+
+                    // The first block in native byte order is the least significant one on
+                    // little-endian targets, and the most significant one on big-endian targets.
+                    self.update_with_iter_scalar((0..NUM_BLOCKS).map(|i| {
+                        let i = if cfg!(target_endian = "little") {
+                            i
+                        } else {
+                            NUM_BLOCKS - 1 - i
+                        };
+                        (int >> (i * <$block_type>::BITS)) as $block_type
+                    }));
+                }
+            )+
+
+            fn finish(&self) -> u64 {
+                let $value = self.value();
+                $finish
+            }
+        }
+    };
+}
+
+impl_hasher!(
+    u16,
+    u8,
+    [write_u8: u8, write_u16: u16, write_u32: u32, write_u64: u64, write_u128: u128],
+    |value| value as u64
+);
+impl_hasher!(
+    u32,
+    u16,
+    [write_u16: u16, write_u32: u32, write_u64: u64, write_u128: u128],
+    |value| value as u64
+);
+impl_hasher!(
+    u64,
+    u32,
+    [write_u32: u32, write_u64: u64, write_u128: u128],
+    |value| value
+);
+// Fold both halves in, since the low half alone would only be `a`.
+impl_hasher!(
+    u128,
+    u64,
+    [write_u64: u64, write_u128: u128],
+    |value| (value >> 64) as u64 ^ value as u64
+);
@@ -0,0 +1,136 @@
+//! Iterator adapters that checksum elements as they pass through.
+
+use {
+    crate::{update_fletcher_scalar, Fletcher, FletcherChecksum, U256},
+    core::{
+        fmt::{self, Debug, Formatter},
+        iter::{FusedIterator, Iterator},
+    },
+};
+
+/// An iterator adapter that feeds every element it yields into a [`Fletcher`] checksum.
+///
+/// This is created by [`FletcherIteratorExt::fletcher_tee`]. Once the adapter has been consumed,
+/// [`value`](ChecksummingIter::value) returns the checksum of every element that was yielded.
+///
+/// # Examples
+///
+/// ```
+/// use fletcher_simd::{Fletcher64, FletcherIteratorExt};
+///
+/// let data = [0x64636261u32, 0x68676665];
+///
+/// let mut tee = data.iter().copied().fletcher_tee::<u64>();
+/// let passed_through: Vec<u32> = tee.by_ref().collect();
+///
+/// let mut fletcher = Fletcher64::new();
+/// fletcher.update_with_iter(data.iter().copied());
+///
+/// assert_eq!(passed_through, data);
+/// assert_eq!(tee.value(), fletcher.value());
+/// ```
+#[derive(Clone)]
+pub struct ChecksummingIter<I, T: FletcherChecksum> {
+    iter: I,
+    fletcher: Fletcher<T>,
+}
+
+impl<I, T> ChecksummingIter<I, T>
+where
+    I: Iterator<Item = T::BlockType>,
+    T: FletcherChecksum,
+{
+    fn new(iter: I) -> Self {
+        Self {
+            iter,
+            fletcher: Fletcher::default(),
+        }
+    }
+
+    /// Returns the checksum of the elements yielded so far.
+    pub fn fletcher(&self) -> Fletcher<T> {
+        Fletcher::<T>::with_initial_values(self.fletcher.a, self.fletcher.b)
+    }
+
+    /// Consumes the adapter, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I, T> Debug for ChecksummingIter<I, T>
+where
+    I: Debug,
+    T: FletcherChecksum,
+    Fletcher<T>: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChecksummingIter")
+            .field("iter", &self.iter)
+            .field("fletcher", &self.fletcher)
+            .finish()
+    }
+}
+
+impl<I, T> Iterator for ChecksummingIter<I, T>
+where
+    I: Iterator<Item = T::BlockType>,
+    T: FletcherChecksum,
+{
+    type Item = T::BlockType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let elem = self.iter.next()?;
+
+        (self.fletcher.a, self.fletcher.b) =
+            update_fletcher_scalar(self.fletcher.a, self.fletcher.b, core::iter::once(elem));
+
+        Some(elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, T> FusedIterator for ChecksummingIter<I, T>
+where
+    I: FusedIterator<Item = T::BlockType>,
+    T: FletcherChecksum,
+{
+}
+
+/// Extension trait adding Fletcher checksum adapters to every [`Iterator`].
+pub trait FletcherIteratorExt: Iterator + Sized {
+    /// Wraps the iterator so that every element it yields is also fed into a Fletcher checksum
+    /// of type `T`.
+    fn fletcher_tee<T>(self) -> ChecksummingIter<Self, T>
+    where
+        T: FletcherChecksum<BlockType = Self::Item>;
+}
+
+impl<I: Iterator> FletcherIteratorExt for I {
+    fn fletcher_tee<T>(self) -> ChecksummingIter<Self, T>
+    where
+        T: FletcherChecksum<BlockType = Self::Item>,
+    {
+        ChecksummingIter::new(self)
+    }
+}
+
+macro_rules! impl_checksumming_iter {
+    ($result_type:ty, $block_type:ty) => {
+        impl<I: Iterator<Item = $block_type>> ChecksummingIter<I, $result_type> {
+            /// Returns the checksum value of the elements yielded so far.
+            pub fn value(&self) -> $result_type {
+                self.fletcher.value()
+            }
+        }
+    };
+}
+
+impl_checksumming_iter!(u16, u8);
+impl_checksumming_iter!(u32, u16);
+impl_checksumming_iter!(u64, u32);
+impl_checksumming_iter!(u128, u64);
+impl_checksumming_iter!(U256, u128);
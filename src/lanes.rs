@@ -0,0 +1,132 @@
+//! Fletcher checksums whose SIMD vector width is chosen at runtime.
+
+use crate::{Fletcher, FletcherChecksum};
+
+/// A Fletcher checksum object that sums with SIMD vectors of a number of lanes chosen at runtime,
+/// e.g. from a tuning configuration. It is implemented for the widths up to 128 bits, which have
+/// SIMD block types.
+///
+/// [`Fletcher::update_with_slice_lanes`] takes the number of lanes as a const generic, so an
+/// unsupported count is a trait-bound error at compile time. This validates the count when the
+/// checksum is constructed instead, with [`checked`](Self::checked), and then dispatches each
+/// update to the matching monomorphization.
+///
+/// The supported counts are the powers of two from 1 to 64, the same as those of
+/// [`core::simd::SupportedLaneCount`]. Each of them works on every target, since vectors wider
+/// than the registers are split across several of them, so no count is rejected based on the
+/// CPU. The checksum is identical for every count.
+///
+/// # Examples
+///
+/// ```
+/// use fletcher_simd::{Fletcher32, LanesFletcher};
+///
+/// let data: Vec<u16> = (0..100).collect();
+///
+/// let mut default_width = Fletcher32::new();
+/// default_width.update_with_slice(&data);
+///
+/// let mut fletcher = LanesFletcher::<u32>::checked(8).unwrap();
+/// fletcher.update_with_slice(&data);
+///
+/// assert_eq!(fletcher.value(), default_width.value());
+/// assert!(LanesFletcher::<u32>::checked(12).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LanesFletcher<T: FletcherChecksum> {
+    fletcher: Fletcher<T>,
+    lanes: usize,
+}
+
+macro_rules! impl_lanes_fletcher {
+    ($result_type:ty, $block_type:ty) => {
+        impl LanesFletcher<$result_type> {
+            /// Constructs a new `LanesFletcher<T>` with the default values that sums with vectors
+            /// of `lanes` blocks, or returns an error if `lanes` is not a supported count.
+            pub fn checked(lanes: usize) -> Result<Self, UnsupportedLanesError> {
+                Self::checked_with_initial_values(lanes, 0, 0)
+            }
+
+            /// Constructs a new `LanesFletcher<T>` with the given initial values that sums with
+            /// vectors of `lanes` blocks, or returns an error if `lanes` is not a supported count.
+            pub fn checked_with_initial_values(
+                lanes: usize,
+                a: $block_type,
+                b: $block_type,
+            ) -> Result<Self, UnsupportedLanesError> {
+                if !is_supported_lane_count(lanes) {
+                    return Err(UnsupportedLanesError { lanes });
+                }
+
+                Ok(Self {
+                    fletcher: Fletcher::<$result_type>::with_initial_values(a, b),
+                    lanes,
+                })
+            }
+
+            /// Updates the checksum with a slice of data of type `T::BlockType`. See
+            /// [`Fletcher::update_with_slice_lanes`].
+            pub fn update_with_slice(&mut self, data: &[$block_type]) {
+                let fletcher = &mut self.fletcher;
+                match self.lanes {
+                    1 => fletcher.update_with_slice_lanes::<1>(data),
+                    2 => fletcher.update_with_slice_lanes::<2>(data),
+                    4 => fletcher.update_with_slice_lanes::<4>(data),
+                    8 => fletcher.update_with_slice_lanes::<8>(data),
+                    16 => fletcher.update_with_slice_lanes::<16>(data),
+                    32 => fletcher.update_with_slice_lanes::<32>(data),
+                    64 => fletcher.update_with_slice_lanes::<64>(data),
+                    _ => unreachable!("the lane count is validated on construction"),
+                }
+            }
+
+            /// Returns the number of blocks in each SIMD vector.
+            pub fn lanes(&self) -> usize {
+                self.lanes
+            }
+
+            /// Returns the underlying checksum.
+            pub fn fletcher(&self) -> Fletcher<$result_type> {
+                self.fletcher
+            }
+
+            /// Returns the checksum value.
+            #[must_use]
+            pub fn value(&self) -> $result_type {
+                self.fletcher.value()
+            }
+        }
+    };
+}
+
+impl_lanes_fletcher!(u16, u8);
+impl_lanes_fletcher!(u32, u16);
+impl_lanes_fletcher!(u64, u32);
+impl_lanes_fletcher!(u128, u64);
+
+/// Whether `lanes` is one of the counts for which `LaneCount<lanes>: SupportedLaneCount` holds.
+fn is_supported_lane_count(lanes: usize) -> bool {
+    lanes.is_power_of_two() && lanes <= 64
+}
+
+/// Error returned when constructing a [`LanesFletcher`] with a number of lanes that is not a
+/// power of two from 1 to 64.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct UnsupportedLanesError {
+    lanes: usize,
+}
+
+impl UnsupportedLanesError {
+    /// Returns the rejected number of lanes.
+    pub fn lanes(&self) -> usize {
+        self.lanes
+    }
+}
+
+impl core::fmt::Display for UnsupportedLanesError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} lanes is not a power of two from 1 to 64", self.lanes)
+    }
+}
+
+impl std::error::Error for UnsupportedLanesError {}
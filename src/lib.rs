@@ -1,34 +1,141 @@
 #![doc = include_str!("../README.md")]
 #![feature(portable_simd)]
 #![feature(stdsimd)]
+// Enabling Neon per function is still unstable on 32-bit ARM, unlike on AArch64.
+#![cfg_attr(target_arch = "arm", feature(arm_target_feature))]
+// The default build is free of unsafe code. Only the features that need it opt back in.
+#![cfg_attr(
+    not(any(feature = "bytemuck", feature = "capi", feature = "maybe_uninit")),
+    forbid(unsafe_code)
+)]
 
 use {
     core::{
         cmp::PartialEq,
-        convert::{From, TryFrom},
+        convert::{From, TryFrom, TryInto},
         fmt::Debug,
-        ops::{Add, AddAssign, IndexMut, Mul},
-        simd::{LaneCount, Simd, SimdElement, SimdUint, SupportedLaneCount},
+        marker::PhantomData,
+        ops::{Add, AddAssign, IndexMut, Mul, Shl, Sub},
+        simd::{LaneCount, Simd, SimdCast, SimdElement, SimdUint, SupportedLaneCount},
     },
-    multiversion::multiversion,
-    num::traits::{Num, Unsigned, WrappingAdd, WrappingMul, WrappingSub},
+    decode::FromBytesVec,
+    num::traits::{AsPrimitive, One, Unsigned, WrappingAdd, WrappingMul, WrappingSub, Zero},
 };
 
 #[cfg(feature = "runtime_dispatch")]
-#[allow(unused_imports)]
-use std::arch::{is_aarch64_feature_detected, is_arm_feature_detected};
+use multiversion::multiversion;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+mod adler32;
+mod any;
+mod batch;
+mod buffered;
+mod builder;
+#[cfg(feature = "capi")]
+pub mod capi;
+mod checked;
+mod checksum;
+mod const_fletcher;
+mod contribution;
+mod counting;
+mod decode;
+mod dispatch;
+mod divergence;
+#[cfg(feature = "experimental")]
+mod experimental;
+mod file;
+mod hasher;
+mod iter;
+mod lanes;
+mod mode;
+mod modular;
+mod rolling;
+mod seeded;
+mod shared;
+mod streaming;
+mod swar;
+mod u256;
+
+#[cfg(feature = "experimental")]
+pub use experimental::ReducingFletcher32;
+pub use {
+    adler32::Adler32,
+    any::{FletcherAny, UnsupportedWidthError},
+    batch::{batch_fletcher128, batch_fletcher16, batch_fletcher32, batch_fletcher64},
+    buffered::BufferedFletcher,
+    builder::{BuildableChecksum, ConfiguredFletcher, FletcherBuilder},
+    checked::CheckedFletcher,
+    checksum::Checksum,
+    const_fletcher::{
+        const_fletcher128, const_fletcher16, const_fletcher256, const_fletcher32, const_fletcher64,
+    },
+    contribution::{
+        fletcher128_contribution, fletcher16_contribution, fletcher256_contribution,
+        fletcher32_contribution, fletcher64_contribution,
+    },
+    counting::CountingFletcher,
+    dispatch::dispatched_target_features,
+    file::{fletcher128_file, fletcher16_file, fletcher256_file, fletcher32_file, fletcher64_file},
+    iter::{ChecksummingIter, FletcherIteratorExt},
+    lanes::{LanesFletcher, UnsupportedLanesError},
+    mode::{Modular, Wrapping},
+    modular::ModularFletcher,
+    rolling::RollingFletcher,
+    seeded::{
+        fletcher128_seeded, fletcher16_seeded, fletcher256_seeded, fletcher32_seeded,
+        fletcher64_seeded,
+    },
+    shared::SharedFletcher,
+    streaming::StreamingFletcher,
+    u256::U256,
+};
+
+// With `runtime_dispatch`, `multiversion` detects CPU features with the standard library macros,
+// which it names unqualified. Unlike `is_x86_feature_detected`, the ARM macros are not in the
+// prelude, so bring them into scope for the dispatcher on the targets that use them.
+#[cfg(all(feature = "runtime_dispatch", target_arch = "aarch64"))]
+use std::arch::is_aarch64_feature_detected;
+#[cfg(all(feature = "runtime_dispatch", target_arch = "arm"))]
+use std::arch::is_arm_feature_detected;
 
 /// Trait for the type representing a certain sized Fletcher checksum.
-pub trait FletcherChecksum: Num + Unsigned + Default {
+///
+/// Implementing this for another type gives [`Fletcher`] its width-independent methods, such as
+/// [`update_with_slice`](Fletcher::update_with_slice) and [`value`](Fletcher::value), for that
+/// type. The rest of the methods are only provided for the built-in checksums.
+pub trait FletcherChecksum: Copy + Default {
     type BlockType: Copy
         + Clone
+        + Debug
         + Default
         + PartialEq
-        + SimdElement
         + TryFrom<usize>
         + Unsigned
         + WrappingAdd
         + WrappingSub;
+
+    /// Size of each block of input in bytes.
+    const BLOCK_BYTES: usize;
+
+    /// Width of the checksum value in bits.
+    const RESULT_BITS: u32;
+
+    /// Packs the two sums into a checksum value, with `b` in the more significant half and `a`
+    /// in the less significant half.
+    fn from_components(a: Self::BlockType, b: Self::BlockType) -> Self;
+
+    /// Updates the two sums with a slice of blocks, returning the new `(a, b)`.
+    ///
+    /// The default sums the blocks one at a time. The built-in checksums override this with their
+    /// SIMD implementation.
+    fn update_components(
+        a: Self::BlockType,
+        b: Self::BlockType,
+        data: &[Self::BlockType],
+    ) -> (Self::BlockType, Self::BlockType) {
+        update_fletcher_scalar(a, b, data.iter().copied())
+    }
 }
 
 /// A Fletcher checksum object that allows for continuous updates to the checksum.
@@ -49,8 +156,8 @@ pub trait FletcherChecksum: Num + Unsigned + Default {
 /// [`Iterator`] interface.
 ///
 /// ```
-/// use byteorder::{ByteOrder, LittleEndian};
 /// use fletcher_simd::Fletcher128;
+/// use std::convert::TryInto;
 ///
 /// const DATA: &str = "abcdefgh";
 /// let mut fletcher = Fletcher128::new();
@@ -58,82 +165,1501 @@ pub trait FletcherChecksum: Num + Unsigned + Default {
 /// fletcher.update_with_iter(
 ///     DATA.as_bytes()
 ///         .chunks(8)
-///         .map(|chunk| LittleEndian::read_u64(chunk)),
+///         .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap())),
 /// );
 ///
 /// assert_eq!(fletcher.value(), 0x68676665646362616867666564636261);
 /// ```
 ///
-#[derive(Copy, Clone, Default, Debug, PartialEq)]
-pub struct Fletcher<T: FletcherChecksum> {
+/// # Layout
+///
+/// `Fletcher<T>` is `#[repr(C)]`: it is `a` followed by `b`, each a `T::BlockType` in native
+/// endianness, with no padding. With the `bytemuck` feature, it implements `bytemuck::Pod` so
+/// that arrays of in-progress states can be reinterpreted as bytes and back.
+///
+/// This layout is stable, so the state can be passed through FFI as a struct of two integers.
+/// The `capi` feature provides `extern "C"` functions operating on it, listed in `capi`.
+///
+/// # Arithmetic
+///
+/// The second type parameter is a marker for how the sums are reduced, so that checksums of the
+/// same width but different arithmetic are different types. It is zero-sized and does not
+/// change the layout. The default, [`Wrapping`], wraps modulo `2^k` and is what every method
+/// documented here implements. [`Modular`] reduces modulo `2^k - 1`, see [`ModularFletcher`],
+/// which is an alias for `Fletcher<T, Modular>`.
+///
+/// ```
+/// use fletcher_simd::{Fletcher, Modular, Wrapping};
+///
+/// let mut wrapping = Fletcher::<u16, Wrapping>::new();
+/// wrapping.update_with_slice(b"abcde");
+///
+/// let mut modular = Fletcher::<u16, Modular>::new();
+/// modular.update_with_slice(b"abcde");
+///
+/// assert_eq!(wrapping.value(), 0xC3EF);
+/// assert_eq!(modular.value(), 0xC8F0);
+/// ```
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct Fletcher<T: FletcherChecksum, M = Wrapping> {
     a: T::BlockType,
     b: T::BlockType,
+    mode: PhantomData<M>,
+}
+
+impl<T: FletcherChecksum, M> Debug for Fletcher<T, M>
+where
+    T::BlockType: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Fletcher")
+            .field("a", &self.a)
+            .field("b", &self.b)
+            .finish()
+    }
+}
+
+/// The two sums of a Fletcher checksum, named by where they end up in
+/// [`value`](Fletcher::value).
+///
+/// This is the same state as [`components`](Fletcher::components), for formats that store the
+/// sums separately rather than as a single integer.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash)]
+pub struct FletcherSums<T: FletcherChecksum> {
+    /// The running sum of the blocks, `a`, stored in the less significant half of the value.
+    pub lower: T::BlockType,
+    /// The running sum of `a`, `b`, stored in the more significant half of the value.
+    pub upper: T::BlockType,
+}
+
+impl<T: FletcherChecksum> Fletcher<T> {
+    /// Size of each block of input in bytes.
+    pub const BLOCK_BYTES: usize = T::BLOCK_BYTES;
+
+    /// Width of the checksum value in bits.
+    pub const RESULT_BITS: u32 = T::RESULT_BITS;
+
+    /// Constructs a new `Fletcher<T>` with the default values.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructs a new `Fletcher<T>` with specific values.
+    ///
+    /// `a` will represent the lesser significant bits.
+    /// `b` will represent the more significant bits.
+    #[inline]
+    pub fn with_initial_values(a: T::BlockType, b: T::BlockType) -> Self {
+        Self {
+            a,
+            b,
+            mode: PhantomData,
+        }
+    }
+
+    /// Constructs a new `Fletcher<T>` with `a` seeded to 1 and `b` to 0.
+    ///
+    /// This is the seeding convention of Adler-32, as specified in [RFC 1950] and used by
+    /// zlib, which some Fletcher variants follow so that leading zero blocks still change
+    /// the checksum. It does not make this an Adler-32 checksum, since the sums still wrap
+    /// modulo `2^k`; use [`Adler32`] for that.
+    ///
+    /// [RFC 1950]: https://www.rfc-editor.org/rfc/rfc1950
+    #[inline]
+    pub fn with_ones_seed() -> Self {
+        Self::with_initial_values(T::BlockType::one(), T::BlockType::zero())
+    }
+
+    /// Resets the checksum to the default values, so that the object can be reused for
+    /// new data.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Updates the checksum with a slice of data of type `T::BlockType`, e.g. `&[u16]` for
+    /// [`Fletcher32`].
+    ///
+    /// This uses [`FletcherChecksum::update_components`], which is SIMD-accelerated for the
+    /// built-in checksums.
+    ///
+    /// Resuming a checksum mid-buffer needs no count of the blocks already consumed. A block's
+    /// weight in `b` is the number of blocks from it to the end, so it depends only on what
+    /// follows, and every update adds the running `a` to `b` once per new block. Only
+    /// [`combine`](Self::combine), which appends a checksum computed separately, needs a count,
+    /// and that is of the appended blocks. The same holds for restoring a saved state with
+    /// [`with_initial_values`](Self::with_initial_values).
+    ///
+    /// ```
+    /// use fletcher_simd::Fletcher16;
+    ///
+    /// let mut resumed = Fletcher16::from(&b"abc"[..]);
+    /// resumed.update_with_slice(b"defgh");
+    ///
+    /// assert_eq!(resumed, Fletcher16::from(&b"abcdefgh"[..]));
+    /// ```
+    #[inline]
+    pub fn update_with_slice(&mut self, data: &[T::BlockType]) {
+        (self.a, self.b) = T::update_components(self.a, self.b, data);
+    }
+
+    /// Updates the checksum with a slice of data of type `T::BlockType`, returning the running
+    /// [`value`](Self::value) after every `mark_every` blocks, e.g. to validate a chunked
+    /// transfer chunk by chunk.
+    ///
+    /// Each mark is the checksum of everything the checksum has been updated with up to that
+    /// point, including earlier updates. A trailing partial chunk is checksummed but not marked,
+    /// so the checksum ends up the same as after [`update_with_slice`](Self::update_with_slice).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mark_every` is 0.
+    ///
+    /// ```
+    /// use fletcher_simd::Fletcher16;
+    ///
+    /// let mut fletcher = Fletcher16::new();
+    /// let marks = fletcher.update_with_marks(b"abcdefgh", 3);
+    ///
+    /// assert_eq!(
+    ///     marks,
+    ///     [Fletcher16::from(&b"abc"[..]).value(), Fletcher16::from(&b"abcdef"[..]).value()]
+    /// );
+    /// assert_eq!(fletcher.value(), 0xF824);
+    /// ```
+    pub fn update_with_marks(&mut self, data: &[T::BlockType], mark_every: usize) -> Vec<T> {
+        assert!(mark_every != 0, "mark_every must be nonzero");
+
+        let chunks = data.chunks_exact(mark_every);
+        let remainder = chunks.remainder();
+
+        let marks = chunks
+            .map(|chunk| {
+                self.update_with_slice(chunk);
+                self.value()
+            })
+            .collect();
+        self.update_with_slice(remainder);
+        marks
+    }
+
+    /// Updates the checksum with an iterator over elements of type `T::BlockType` using
+    /// a scalar-only implementation.
+    pub fn update_with_iter_scalar<Iter>(&mut self, elems: Iter)
+    where
+        Iter: Iterator<Item = T::BlockType>,
+    {
+        (self.a, self.b) = update_fletcher_scalar(self.a, self.b, elems);
+    }
+
+    /// Returns the `(a, b)` sums of the checksum, in the order accepted by
+    /// [`with_initial_values`](Self::with_initial_values) and the `From<(a, b)>`
+    /// conversion, so that a saved state can be restored later.
+    #[inline]
+    pub fn components(&self) -> (T::BlockType, T::BlockType) {
+        (self.a, self.b)
+    }
+
+    /// Returns the two sums of the checksum as a named [`FletcherSums`].
+    ///
+    /// ```
+    /// use fletcher_simd::Fletcher32;
+    ///
+    /// let fletcher = Fletcher32::with_initial_values(0x1234, 0xABCD);
+    /// let sums = fletcher.sums();
+    ///
+    /// assert_eq!((sums.lower, sums.upper), (0x1234, 0xABCD));
+    /// assert_eq!(fletcher.value(), 0xABCD_1234);
+    /// assert_eq!(Fletcher32::from_sums(sums), fletcher);
+    /// ```
+    pub fn sums(&self) -> FletcherSums<T> {
+        FletcherSums {
+            lower: self.a,
+            upper: self.b,
+        }
+    }
+
+    /// Constructs a checksum from its two sums, as returned by [`sums`](Self::sums).
+    pub fn from_sums(sums: FletcherSums<T>) -> Self {
+        Self::with_initial_values(sums.lower, sums.upper)
+    }
+
+    /// Returns the checksum value.
+    ///
+    /// A checksum that has not been updated has a value of 0, which is also the value
+    /// after only zero blocks. Callers that need to tell these apart have to track the
+    /// input length themselves.
+    #[must_use]
+    #[inline]
+    pub fn value(&self) -> T {
+        T::from_components(self.a, self.b)
+    }
+
+    /// Consumes the checksum and returns its value.
+    ///
+    /// Use [`value`](Self::value) instead to keep updating the checksum afterwards.
+    #[must_use]
+    #[inline]
+    pub fn finalize(self) -> T {
+        self.value()
+    }
+}
+
+/// Byte order used to group bytes into blocks wider than a byte.
+///
+/// Fletcher's checksum is defined over blocks rather than bytes, so the same bytes give different
+/// checksums depending on how they are grouped. The default, used where no byte order is
+/// passed, is [`DEFAULT`](Self::DEFAULT).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// The first byte of each block is the least significant.
+    Little,
+    /// The first byte of each block is the most significant.
+    Big,
+}
+
+impl Endianness {
+    /// The byte order used where none is passed: by [`Default`], by the `TryFrom<&[u8]>`
+    /// conversions of [`Fletcher`], by [`FletcherAny::update_with_slice`], by the `const` and file
+    /// functions such as [`const_fletcher32`] and [`fletcher32_file`], and by [`FletcherBuilder`]
+    /// unless [`endianness`](FletcherBuilder::endianness) is set.
+    ///
+    /// This is little-endian, or big-endian with the `be_default` feature, for codebases where
+    /// big-endian input dominates. Methods that take the byte order, or name it like
+    /// [`update_with_slice_le`](Fletcher::update_with_slice_le), are not affected.
+    pub const DEFAULT: Self = if cfg!(feature = "be_default") {
+        Self::Big
+    } else {
+        Self::Little
+    };
+
+    /// The byte order of the target, in which blocks are laid out in memory.
+    pub const NATIVE: Self = if cfg!(target_endian = "big") {
+        Self::Big
+    } else {
+        Self::Little
+    };
+}
+
+impl Default for Endianness {
+    /// Returns [`Endianness::DEFAULT`].
+    fn default() -> Self {
+        Self::DEFAULT
+    }
 }
 
 /// Currently, limit vector sizes to 256 bits. In the future, this may bump up to 512 bits for
 /// AVX-512.
+///
+/// Every width uses `MAX_VEC_SIZE / BLOCK_BYTES` lanes by default, so this must be a power of two
+/// no larger than 64 lanes of the narrowest block, which is 64 bytes. Any other value fails to
+/// compile with `LaneCount<N>: SupportedLaneCount` not being satisfied, where `N` is the offending
+/// lane count.
 const MAX_VEC_SIZE: usize = 256 / 8;
 
+/// Size of the buffer that [`Fletcher::update_with_reader`] reads into, which is a multiple of
+/// every block size.
+const READ_BUF_BYTES: usize = 64 * 1024;
+
+/// Number of bytes of input each task handles in the parallel update methods.
+///
+/// This has not been tuned. `cargo bench --features rayon -- par/` compares serial and parallel
+/// updates at sizes around it.
+#[cfg(feature = "rayon")]
+pub const PAR_CHUNK_BYTES: usize = 256 * 1024;
+
+/// Macro to implement the parts of [`Fletcher`] that are the same for every width, regardless of
+/// how the blocks are summed.
+macro_rules! impl_fletcher_common {
+    ($result_type:ty, $block_type:ty, $block_size:literal) => {
+        // Catch mismatched instantiations at compile time rather than as wrong checksums.
+        const _: () = assert!(
+            core::mem::size_of::<$block_type>() * 2 == core::mem::size_of::<$result_type>(),
+            "the result type must be exactly twice the size of the block type"
+        );
+        const _: () = assert!(
+            core::mem::size_of::<$block_type>() == $block_size,
+            "the block size must match the size of the block type"
+        );
+
+        impl Fletcher<$result_type> {
+            /// Checksums each item independently and returns the checksums in order.
+            ///
+            /// ```
+            /// use fletcher_simd::Fletcher16;
+            ///
+            /// let records: Vec<&[u8]> = vec![b"abcde", b"", b"abcdefgh"];
+            ///
+            /// assert_eq!(Fletcher16::checksum_each(records), vec![0xC3EF, 0, 0xF824]);
+            /// ```
+            pub fn checksum_each<'a, I>(items: I) -> Vec<$result_type>
+            where
+                I: IntoIterator<Item = &'a [$block_type]>,
+            {
+                let mut fletcher = Self::new();
+
+                items
+                    .into_iter()
+                    .map(|item| {
+                        fletcher.reset();
+                        fletcher.update_with_slice(item);
+                        fletcher.value()
+                    })
+                    .collect()
+            }
+
+            /// Checksums each item independently on the [`rayon`] thread pool and returns the
+            /// checksums in order.
+            ///
+            /// The result is identical to [`checksum_each`](Self::checksum_each). This pays off
+            /// when there are many items; a single large item is better served by
+            /// [`par_update_with_slice`](Self::par_update_with_slice).
+            #[cfg(feature = "rayon")]
+            pub fn par_checksum_each<'a, I>(items: I) -> Vec<$result_type>
+            where
+                I: IntoParallelIterator<Item = &'a [$block_type]>,
+            {
+                items
+                    .into_par_iter()
+                    .map(|item| {
+                        let mut fletcher = Self::new();
+                        fletcher.update_with_slice(item);
+                        fletcher.value()
+                    })
+                    .collect()
+            }
+
+            /// Updates the checksum with anything that can be viewed as a slice of
+            /// `T::BlockType` and returns `self`, so that calls can be chained.
+            ///
+            /// For [`Fletcher16`], this accepts byte containers like `String`, `&str`, `Vec<u8>`
+            /// and `[u8; N]` directly. Wider checksums take byte input through
+            /// [`update_with_bytes`](Self::update_with_bytes).
+            ///
+            /// ```
+            /// use fletcher_simd::Fletcher16;
+            ///
+            /// let checksum = Fletcher16::new()
+            ///     .update(String::from("ab"))
+            ///     .update("cd")
+            ///     .update([b'e', b'f'])
+            ///     .update(b"gh")
+            ///     .value();
+            ///
+            /// let mut streamed = Fletcher16::new();
+            /// streamed.update_with_slice(b"abcdefgh");
+            ///
+            /// assert_eq!(checksum, streamed.value());
+            /// ```
+            pub fn update<D: AsRef<[$block_type]>>(&mut self, data: D) -> &mut Self {
+                self.update_with_slice(data.as_ref());
+                self
+            }
+
+            /// Returns a copy of the checksum updated with a slice of data of type
+            /// `T::BlockType`, leaving `self` untouched.
+            ///
+            /// Since the checksum is `Copy`, this is cheap, which makes it convenient for trying
+            /// out several continuations of the same prefix.
+            ///
+            /// ```
+            /// use fletcher_simd::Fletcher16;
+            ///
+            /// let prefix = Fletcher16::new().chain_update(b"abc");
+            /// let left = prefix.chain_update(b"de");
+            /// let right = prefix.chain_update(b"xy");
+            ///
+            /// assert_eq!(prefix, Fletcher16::new().chain_update(b"abc"));
+            /// assert_eq!(left.value(), Fletcher16::new().chain_update(b"abcde").value());
+            /// assert_ne!(left, right);
+            /// ```
+            #[must_use]
+            pub fn chain_update(mut self, data: &[$block_type]) -> Self {
+                self.update_with_slice(data);
+                self
+            }
+
+            /// Updates the checksum with the first `init_len` elements of a partially initialized
+            /// buffer, such as one filled by a reader, without copying them out first.
+            ///
+            /// This requires the `maybe_uninit` feature, since it is unsafe.
+            ///
+            /// When the buffer can be initialized up front, e.g. zeroed once and reused, there is
+            /// no need for this: pass the filled part to [`update_with_slice`] instead, as in
+            /// `fletcher.update_with_slice(&buf[..filled_len])`. This is for buffers that are
+            /// deliberately left uninitialized, where the only way to get an initialized slice is
+            /// to trust the writer's count.
+            ///
+            /// ```
+            /// use core::mem::MaybeUninit;
+            /// use fletcher_simd::Fletcher16;
+            ///
+            /// let mut buf = [MaybeUninit::<u8>::uninit(); 64];
+            /// let filled_len = 5;
+            /// for (slot, &byte) in buf.iter_mut().zip(b"abcde") {
+            ///     slot.write(byte);
+            /// }
+            ///
+            /// let mut fletcher = Fletcher16::new();
+            /// // SAFETY: The first `filled_len` bytes were written above.
+            /// unsafe { fletcher.update_with_maybe_uninit(&buf, filled_len) };
+            ///
+            /// assert_eq!(fletcher.value(), 0xC3EF);
+            /// ```
+            ///
+            /// [`update_with_slice`]: Self::update_with_slice
+            ///
+            /// # Safety
+            ///
+            /// The first `init_len` elements of `buf` must be initialized.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `init_len` is greater than `buf.len()`.
+            #[cfg(feature = "maybe_uninit")]
+            pub unsafe fn update_with_maybe_uninit(
+                &mut self,
+                buf: &[core::mem::MaybeUninit<$block_type>],
+                init_len: usize,
+            ) {
+                assert!(
+                    init_len <= buf.len(),
+                    "initialized length {} exceeds buffer length {}",
+                    init_len,
+                    buf.len()
+                );
+
+                // SAFETY: `MaybeUninit<T>` has the same layout as `T`, the length is in bounds,
+                // and the caller guarantees that the first `init_len` elements are initialized.
+                let init =
+                    core::slice::from_raw_parts(buf.as_ptr().cast::<$block_type>(), init_len);
+                self.update_with_slice(init);
+            }
+
+            /// Updates the checksum with a slice of bytes, interpreting each group of
+            /// `size_of::<T::BlockType>()` bytes as a little-endian block.
+            ///
+            /// See [`update_with_bytes`](Self::update_with_bytes) for details.
+            pub fn update_with_slice_le(&mut self, bytes: &[u8]) {
+                self.update_with_bytes(bytes, Endianness::Little);
+            }
+
+            /// Updates the checksum with a slice of bytes, interpreting each group of
+            /// `size_of::<T::BlockType>()` bytes as a big-endian block.
+            ///
+            /// See [`update_with_bytes`](Self::update_with_bytes) for details.
+            pub fn update_with_slice_be(&mut self, bytes: &[u8]) {
+                self.update_with_bytes(bytes, Endianness::Big);
+            }
+
+            /// Updates the checksum with a slice of data of type `T::BlockType`, splitting it
+            /// into chunks that are checksummed in parallel and then folded together with
+            /// [`combine`](Self::combine).
+            ///
+            /// The result is identical to [`update_with_slice`](Self::update_with_slice). Slices
+            /// no longer than [`PAR_CHUNK_BYTES`] make up a single chunk, so they are checksummed
+            /// serially on the calling thread.
+            #[cfg(feature = "rayon")]
+            pub fn par_update_with_slice(&mut self, data: &[$block_type]) {
+                const CHUNK_LEN: usize = PAR_CHUNK_BYTES / $block_size;
+
+                if data.len() <= CHUNK_LEN {
+                    self.update_with_slice(data);
+                    return;
+                }
+
+                let (other, other_len) = data
+                    .par_chunks(CHUNK_LEN)
+                    .map(|chunk| {
+                        let mut fletcher = Self::new();
+                        fletcher.update_with_slice(chunk);
+                        (fletcher, chunk.len())
+                    })
+                    .reduce(
+                        || (Self::new(), 0),
+                        |(mut acc, acc_len), (other, other_len)| {
+                            acc.combine(&other, other_len);
+                            (acc, acc_len + other_len)
+                        },
+                    );
+
+                self.combine(&other, other_len);
+            }
+
+            /// Reverses the last update with a single block, given the value of that block.
+            ///
+            /// This only undoes the most recent block, and only if `elem` is exactly the block
+            /// that was added; otherwise the state is silently corrupted. Earlier blocks can be
+            /// removed by calling this repeatedly, most recent first. To remove blocks from the
+            /// front instead, see [`RollingFletcher`].
+            ///
+            /// ```
+            /// use fletcher_simd::Fletcher16;
+            ///
+            /// let mut fletcher = Fletcher16::new();
+            /// fletcher.update_with_slice(b"payload");
+            ///
+            /// let mut framed = fletcher;
+            /// framed.update_with_slice(&[7]);
+            /// framed.unupdate_last(7);
+            ///
+            /// assert_eq!(framed, fletcher);
+            /// ```
+            pub fn unupdate_last(&mut self, elem: $block_type) {
+                self.b = self.b.wrapping_sub(self.a);
+                self.a = self.a.wrapping_sub(elem);
+            }
+
+            /// Combines the checksum with the checksum of data that follows it, as if the data
+            /// `other` was computed over had been appended to this checksum's data.
+            ///
+            /// `other` must have been computed starting from [`new`](Self::new), and `other_len`
+            /// is the number of blocks it was computed over. The length is required because every
+            /// block of `other` is shifted `other_len` positions further from the end of the
+            /// combined data, which adds `other_len * a` to `b`.
+            ///
+            /// ```
+            /// use fletcher_simd::Fletcher16;
+            ///
+            /// const DATA: &str = "abcdefgh";
+            /// let (first, second) = DATA.as_bytes().split_at(3);
+            ///
+            /// let mut fletcher = Fletcher16::new();
+            /// fletcher.update_with_slice(first);
+            ///
+            /// let mut other = Fletcher16::new();
+            /// other.update_with_slice(second);
+            ///
+            /// fletcher.combine(&other, second.len());
+            /// assert_eq!(fletcher.value(), 0xF824);
+            /// ```
+            pub fn combine(&mut self, other: &Self, other_len: usize) {
+                self.combine_reduced(other, other_len as $block_type);
+            }
+
+            /// [`combine`](Self::combine) with the length of `other` already reduced to the block
+            /// type, e.g. by truncating it with `as`.
+            ///
+            /// Truncating the length is correct since all of the arithmetic wraps modulo the block
+            /// size, so only the length modulo `2^k` affects the result. `a` is carried into `b`
+            /// once for each block of `other`, shifting the weights of `other` past `self`.
+            #[inline]
+            pub(crate) fn combine_reduced(&mut self, other: &Self, other_len: $block_type) {
+                let shift = other_len.wrapping_mul(self.a);
+
+                self.a = self.a.wrapping_add(other.a);
+                self.b = self.b.wrapping_add(other.b).wrapping_add(shift);
+            }
+
+            /// Updates the checksum with `count` copies of `elem`, in constant time.
+            ///
+            /// After `n` copies of `x`, `a` has grown by `n * x` and `b` by `n * a` plus
+            /// `x * n * (n + 1) / 2`, so there is no need to loop over the run. This is the same
+            /// as updating with a slice of `count` copies of `elem`.
+            ///
+            /// ```
+            /// use fletcher_simd::Fletcher32;
+            ///
+            /// let mut repeated = Fletcher32::new();
+            /// repeated.update_repeated(0xFFFF, 1000);
+            ///
+            /// let mut looped = Fletcher32::new();
+            /// looped.update_with_slice(&[0xFFFF; 1000]);
+            ///
+            /// assert_eq!(repeated, looped);
+            /// ```
+            pub fn update_repeated(&mut self, elem: $block_type, count: usize) {
+                // Truncating is correct for the same reason as in `combine_reduced`, as long as the
+                // division by two happens first, on whichever factor is even.
+                let triangle = if count % 2 == 0 {
+                    ((count / 2) as $block_type)
+                        .wrapping_mul((count as $block_type).wrapping_add(1))
+                } else {
+                    (count as $block_type).wrapping_mul((count / 2 + 1) as $block_type)
+                };
+                let count = count as $block_type;
+
+                self.b = self
+                    .b
+                    .wrapping_add(count.wrapping_mul(self.a))
+                    .wrapping_add(triangle.wrapping_mul(elem));
+                self.a = self.a.wrapping_add(count.wrapping_mul(elem));
+            }
+
+            /// Returns the checksum value with the input length mixed in, as if the bytes of `len`
+            /// as a little-endian `u64` had been appended, grouped into blocks as with
+            /// [`update_with_bytes`](Self::update_with_bytes).
+            ///
+            /// This is not part of Fletcher's checksum and will not match other implementations.
+            /// It is meant for using the checksum as a hash, where inputs that only differ in a
+            /// run of zero blocks, and so share a [`value`](Self::value), should be told apart.
+            /// The checksum does not track its length, so `len` is the number of blocks the
+            /// caller has fed it.
+            ///
+            /// ```
+            /// use fletcher_simd::Fletcher16;
+            ///
+            /// let one_zero = Fletcher16::from(&[0][..]);
+            /// let two_zeros = Fletcher16::from(&[0, 0][..]);
+            ///
+            /// assert_eq!(one_zero.value(), two_zeros.value());
+            /// assert_ne!(one_zero.value_with_length(1), two_zeros.value_with_length(2));
+            /// ```
+            #[must_use]
+            pub fn value_with_length(&self, len: usize) -> $result_type {
+                self.value_with_length_u64(len as u64)
+            }
+
+            /// [`value_with_length`](Self::value_with_length) for lengths kept as a `u64`, which
+            /// may not fit in a `usize`.
+            pub(crate) fn value_with_length_u64(&self, len: u64) -> $result_type {
+                let mut mixed = *self;
+                mixed.update_with_iter_scalar(len.to_le_bytes().chunks($block_size).map(|chunk| {
+                    let mut block = [0u8; $block_size];
+                    block[..chunk.len()].copy_from_slice(chunk);
+                    <$block_type>::from_le_bytes(block)
+                }));
+                mixed.value()
+            }
+
+            /// Updates the checksum with every byte read from `reader` until the end of the
+            /// input, grouped into blocks in the given byte order as with
+            /// [`update_with_bytes`](Self::update_with_bytes).
+            ///
+            /// Reads go through an internal buffer, so `reader` does not need to be buffered, and
+            /// blocks split across reads are joined back together. Only the final partial block,
+            /// if any, is zero-padded. Reads that fail with [`std::io::ErrorKind::Interrupted`]
+            /// are retried. Any other error is returned, with the checksum holding the blocks
+            /// read before it.
+            ///
+            /// ```
+            /// use fletcher_simd::{Endianness, Fletcher16};
+            ///
+            /// let mut fletcher = Fletcher16::new();
+            /// fletcher.update_with_reader(&b"abcdefgh"[..], Endianness::Little).unwrap();
+            ///
+            /// assert_eq!(fletcher.value(), 0xF824);
+            /// ```
+            pub fn update_with_reader<R: std::io::Read>(
+                &mut self,
+                mut reader: R,
+                endianness: Endianness,
+            ) -> std::io::Result<()> {
+                let block_bytes = <$result_type as FletcherChecksum>::BLOCK_BYTES;
+                let mut buf = vec![0; READ_BUF_BYTES];
+                let mut filled = 0;
+
+                loop {
+                    match reader.read(&mut buf[filled..]) {
+                        Ok(0) => break,
+                        Ok(read) => filled += read,
+                        Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                        Err(err) => return Err(err),
+                    }
+
+                    // Keep a trailing partial block for the next read to complete.
+                    let whole = filled - filled % block_bytes;
+                    self.update_with_bytes(&buf[..whole], endianness);
+                    buf.copy_within(whole..filled, 0);
+                    filled -= whole;
+                }
+
+                self.update_with_bytes(&buf[..filled], endianness);
+                Ok(())
+            }
+
+            /// Updates the checksum with every byte of `reader` until the end of the input, as
+            /// with [`update_with_reader`](Self::update_with_reader), and returns the number of
+            /// bytes consumed.
+            ///
+            /// The bytes are checksummed straight out of the reader's own buffer with
+            /// [`fill_buf`](std::io::BufRead::fill_buf) and
+            /// [`consume`](std::io::BufRead::consume), instead of being copied into another
+            /// buffer. Only a block split across two fills is copied, to join it back together.
+            /// If an error is returned, the checksum holds the whole blocks consumed before it.
+            ///
+            /// ```
+            /// use fletcher_simd::{Endianness, Fletcher16};
+            /// use std::io::BufReader;
+            ///
+            /// let mut fletcher = Fletcher16::new();
+            /// let mut reader = BufReader::new(&b"abcdefgh"[..]);
+            ///
+            /// assert_eq!(fletcher.update_with_bufread(&mut reader, Endianness::Little).unwrap(), 8);
+            /// assert_eq!(fletcher.value(), 0xF824);
+            /// ```
+            pub fn update_with_bufread<R: std::io::BufRead>(
+                &mut self,
+                reader: &mut R,
+                endianness: Endianness,
+            ) -> std::io::Result<u64> {
+                let block_bytes = <$result_type as FletcherChecksum>::BLOCK_BYTES;
+                let mut partial = [0u8; $block_size];
+                let mut partial_len = 0;
+                let mut total = 0;
+
+                loop {
+                    let mut span = match reader.fill_buf() {
+                        Ok([]) => break,
+                        Ok(span) => span,
+                        Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                        Err(err) => return Err(err),
+                    };
+                    let span_len = span.len();
+
+                    // Complete a block left over from the previous span first.
+                    if partial_len > 0 {
+                        let take = (block_bytes - partial_len).min(span.len());
+                        partial[partial_len..partial_len + take].copy_from_slice(&span[..take]);
+                        partial_len += take;
+                        span = &span[take..];
+
+                        if partial_len == block_bytes {
+                            self.update_with_bytes(&partial, endianness);
+                            partial_len = 0;
+                        }
+                    }
+
+                    let whole = span.len() - span.len() % block_bytes;
+                    self.update_with_bytes(&span[..whole], endianness);
+                    partial[partial_len..partial_len + span.len() - whole]
+                        .copy_from_slice(&span[whole..]);
+                    partial_len += span.len() - whole;
+
+                    reader.consume(span_len);
+                    total += span_len as u64;
+                }
+
+                self.update_with_bytes(&partial[..partial_len], endianness);
+                Ok(total)
+            }
+
+            /// Returns a 64-bit hash of the checksum value, mixed so that every bit depends on
+            /// both sums, for use as a key in hash tables.
+            ///
+            /// The low bits of [`value`](Self::value) come only from `a`, which is a plain sum of
+            /// the blocks, so similar inputs crowd into the same buckets. This runs each 64-bit
+            /// word of the value through the `SplitMix64` finalizer. For checksums of up to 64
+            /// bits that is a bijection, so it adds no collisions. This is only for spreading
+            /// keys: it adds nothing to the checksum's ability to detect errors, and
+            /// [`value`](Self::value) is unchanged.
+            #[must_use]
+            pub fn hash_value(&self) -> u64 {
+                self.value().to_le_bytes().chunks(8).fold(0, |hash, word| {
+                    let mut padded = [0; 8];
+                    padded[..word.len()].copy_from_slice(word);
+                    mix64(hash ^ u64::from_le_bytes(padded))
+                })
+            }
+
+            /// Formats the checksum value in the given radix, zero-padded to the number of digits
+            /// of the largest value of `T`, e.g. 5 decimal digits for a 16-bit checksum.
+            ///
+            /// Digits above 9 are lowercase letters, as with the `{:x}` format.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `radix` is not in the range from 2 to 36.
+            ///
+            /// ```
+            /// use fletcher_simd::Fletcher16;
+            ///
+            /// let fletcher = Fletcher16::from(&b"abcde"[..]);
+            ///
+            /// assert_eq!(fletcher.format_radix(16), "c3ef");
+            /// assert_eq!(fletcher.format_radix(10), "50159");
+            /// assert_eq!(Fletcher16::new().format_radix(10), "00000");
+            /// ```
+            pub fn format_radix(&self, radix: u32) -> String {
+                format_be_bytes_radix(&self.value().to_be_bytes(), radix)
+            }
+
+            /// Appends the bytes of the checksum value to `buf` in the given byte order, e.g. as
+            /// the trailer of a frame whose payload is already in `buf`.
+            ///
+            /// ```
+            /// use fletcher_simd::{Endianness, Fletcher16};
+            ///
+            /// let mut frame = b"abcde".to_vec();
+            /// Fletcher16::from(&frame[..]).append_to(&mut frame, Endianness::Big);
+            ///
+            /// assert_eq!(frame, b"abcde\xC3\xEF");
+            /// assert!(Fletcher16::verify_framed(&frame, Endianness::Big));
+            /// ```
+            pub fn append_to(&self, buf: &mut Vec<u8>, endianness: Endianness) {
+                let value = self.value();
+                match endianness {
+                    Endianness::Little => buf.extend_from_slice(&value.to_le_bytes()),
+                    Endianness::Big => buf.extend_from_slice(&value.to_be_bytes()),
+                }
+            }
+
+            /// Checks a frame written by [`append_to`](Self::append_to), whose last
+            /// `size_of::<T>()` bytes are the checksum value of the bytes before them.
+            ///
+            /// The payload is checksummed as with [`update_with_bytes`](Self::update_with_bytes),
+            /// so the same byte order is used for its blocks and for the trailer. Returns `false`
+            /// if `framed` is too short to hold a trailer.
+            #[must_use]
+            pub fn verify_framed(framed: &[u8], endianness: Endianness) -> bool {
+                const TRAILER_BYTES: usize = core::mem::size_of::<$result_type>();
+
+                if framed.len() < TRAILER_BYTES {
+                    return false;
+                }
+
+                let (payload, trailer) = framed.split_at(framed.len() - TRAILER_BYTES);
+                let trailer: [u8; TRAILER_BYTES] = trailer.try_into().unwrap();
+                let expected = match endianness {
+                    Endianness::Little => <$result_type>::from_le_bytes(trailer),
+                    Endianness::Big => <$result_type>::from_be_bytes(trailer),
+                };
+
+                let mut fletcher = Self::new();
+                fletcher.update_with_bytes(payload, endianness);
+                fletcher.value() == expected
+            }
+        }
+
+        // SAFETY: `Fletcher<T>` is `#[repr(C)]` with two fields of the same integer type, so it
+        // has no padding, and every bit pattern, including all zeroes, is a valid state.
+        #[cfg(feature = "bytemuck")]
+        unsafe impl bytemuck::Zeroable for Fletcher<$result_type> {}
+        #[cfg(feature = "bytemuck")]
+        unsafe impl bytemuck::Pod for Fletcher<$result_type> {}
+
+        /// Generates a checksum with arbitrary sums, e.g. as a state read back from storage, for
+        /// fuzzing code that consumes checksums.
+        #[cfg(feature = "arbitrary")]
+        impl<'a> arbitrary::Arbitrary<'a> for Fletcher<$result_type> {
+            fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                Ok(Self::with_initial_values(u.arbitrary()?, u.arbitrary()?))
+            }
+
+            fn size_hint(depth: usize) -> (usize, Option<usize>) {
+                <($block_type, $block_type)>::size_hint(depth)
+            }
+        }
+
+        /// Overwrites both sums with zeroes, which leaves the checksum in its default state.
+        #[cfg(feature = "zeroize")]
+        impl zeroize::Zeroize for Fletcher<$result_type> {
+            fn zeroize(&mut self) {
+                self.a.zeroize();
+                self.b.zeroize();
+            }
+        }
+
+        /// Folds an iterator of blocks into a checksum, as with
+        /// [`update_with_iter`](Fletcher::update_with_iter) on a new checksum.
+        ///
+        /// ```
+        /// use fletcher_simd::Fletcher16;
+        ///
+        /// let fletcher: Fletcher16 = b"abcdefgh".iter().copied().sum();
+        ///
+        /// assert_eq!(fletcher, Fletcher16::from(&b"abcdefgh"[..]));
+        /// ```
+        impl core::iter::Sum<$block_type> for Fletcher<$result_type> {
+            fn sum<I: Iterator<Item = $block_type>>(iter: I) -> Self {
+                let mut fletcher = Self::new();
+                fletcher.update_with_iter(iter);
+                fletcher
+            }
+        }
+
+        /// Folds an iterator of references to blocks into a checksum, as with
+        /// [`update_with_iter`](Fletcher::update_with_iter) on a new checksum.
+        impl<'a> core::iter::Sum<&'a $block_type> for Fletcher<$result_type> {
+            fn sum<I: Iterator<Item = &'a $block_type>>(iter: I) -> Self {
+                iter.copied().sum()
+            }
+        }
+
+        /// Orders checksums by their combined [`value`](Fletcher::value), so `b` is compared
+        /// before `a`.
+        impl Ord for Fletcher<$result_type> {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.value().cmp(&other.value())
+            }
+        }
+
+        impl PartialOrd for Fletcher<$result_type> {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        /// Combines the checksum with the checksum of data that follows it, taking the checksum
+        /// along with the number of blocks it was computed over. This is equivalent to
+        /// [`combine`](Fletcher::combine).
+        ///
+        /// A plain `+=` of two checksums is not possible, because the blocks of the appended data
+        /// move further from the end of the combined data, so `b` depends on their count.
+        ///
+        /// ```
+        /// use fletcher_simd::Fletcher16;
+        ///
+        /// let mut fletcher = Fletcher16::new();
+        /// fletcher.update_with_slice(b"abc");
+        ///
+        /// let mut part = Fletcher16::new();
+        /// part.update_with_slice(b"defgh");
+        ///
+        /// fletcher += (part, 5);
+        /// assert_eq!(fletcher.value(), 0xF824);
+        /// ```
+        impl AddAssign<(Fletcher<$result_type>, usize)> for Fletcher<$result_type> {
+            fn add_assign(&mut self, (other, other_len): (Self, usize)) {
+                self.combine(&other, other_len);
+            }
+        }
+
+        /// Constructs a checksum from its `(a, b)` sums, as returned by
+        /// [`components`](Fletcher::components).
+        impl From<($block_type, $block_type)> for Fletcher<$result_type> {
+            fn from((a, b): ($block_type, $block_type)) -> Self {
+                Self::with_initial_values(a, b)
+            }
+        }
+
+        impl From<Fletcher<$result_type>> for $result_type {
+            fn from(f: Fletcher<$result_type>) -> Self {
+                f.value()
+            }
+        }
+
+        /// Converts the checksum into the bytes of its value in big-endian byte order, so that
+        /// the bytes of `b` come before those of `a`.
+        ///
+        /// This is the same as [`append_to`](Fletcher::append_to) with [`Endianness::Big`].
+        ///
+        /// ```
+        /// use fletcher_simd::Fletcher16;
+        ///
+        /// let bytes: [u8; 2] = Fletcher16::from(&b"abcde"[..]).into();
+        /// assert_eq!(bytes, [0xC3, 0xEF]);
+        /// ```
+        impl From<Fletcher<$result_type>> for [u8; core::mem::size_of::<$result_type>()] {
+            fn from(f: Fletcher<$result_type>) -> Self {
+                f.value().to_be_bytes()
+            }
+        }
+    };
+}
+
 /// Macro to implement [`Fletcher`] since the SIMD interface does not play well with inherent
 /// associated types and outside generics.
 macro_rules! impl_fletcher {
-    ($result_type:ty, $block_type:ty, $block_size:literal) => {
+    (
+        $result_type:ty,
+        $block_type:ty,
+        $signed_type:ty,
+        $block_size:literal,
+        $simd_threshold:literal
+    ) => {
+        impl_fletcher_common!($result_type, $block_type, $block_size);
+
         impl FletcherChecksum for $result_type {
             type BlockType = $block_type;
+
+            const BLOCK_BYTES: usize = $block_size;
+            const RESULT_BITS: u32 = <$result_type>::BITS;
+
+            #[inline]
+            fn from_components(a: $block_type, b: $block_type) -> Self {
+                const SHIFT_SIZE: usize = core::mem::size_of::<$block_type>() * 8;
+
+                ((b as $result_type) << SHIFT_SIZE) | a as $result_type
+            }
+
+            #[inline]
+            fn update_components(
+                a: $block_type,
+                b: $block_type,
+                data: &[$block_type],
+            ) -> ($block_type, $block_type) {
+                let mut fletcher = Fletcher::<$result_type>::with_initial_values(a, b);
+                fletcher
+                    .update_with_slice_threshold(data, Fletcher::<$result_type>::SIMD_THRESHOLD);
+                fletcher.components()
+            }
         }
 
         impl Fletcher<$result_type> {
-            /// Constructs a new `Fletcher<T>` with the default values.
-            pub fn new() -> Self {
-                Self::default()
+            /// Number of blocks in each SIMD vector of the default width, which fills 256 bits.
+            ///
+            /// This is the same whichever instruction set is chosen, see
+            /// [`dispatched_target_features`](crate::dispatched_target_features). Where the
+            /// registers are narrower, each vector is split across several of them.
+            pub const LANES: usize = MAX_VEC_SIZE / $block_size;
+
+            /// Minimum number of blocks for which [`update_with_slice`](Self::update_with_slice)
+            /// uses the SIMD implementation. Shorter slices are summed one block at a time, since
+            /// for them, loading the vectors and weighting their lanes costs more than it saves.
+            ///
+            /// The defaults are where the SIMD implementation overtakes the scalar one on x86-64
+            /// with AVX2, i.e. the shortest measured input at which it is at least as fast. Times
+            /// in nanoseconds per call, scalar / SIMD, by input length in vectors of
+            /// [`LANES`](Self::LANES) blocks:
+            ///
+            /// | Checksum      | 1 vector | 2 vectors | 4 vectors | 8 vectors | 12 vectors | Threshold |
+            /// |---------------|----------|-----------|-----------|-----------|------------|-----------|
+            /// | `Fletcher16`  | 9 / 9    | 20 / 10   | 44 / 12   | 96 / 14   | 153 / 20   | 32 blocks |
+            /// | `Fletcher32`  | 11 / 9   | 15 / 10   | 29 / 11   | 54 / 15   | 82 / 21    | 16 blocks |
+            /// | `Fletcher64`  | 5 / 11   | 11 / 10   | 14 / 12   | 31 / 15   | 38 / 19    | 16 blocks |
+            /// | `Fletcher128` | 3 / 9    | 5 / 11    | 8 / 13    | 14 / 17   | 22 / 20    | 48 blocks |
+            ///
+            /// To use a different threshold, e.g. one measured on another CPU, call
+            /// [`update_with_slice_threshold`](Self::update_with_slice_threshold).
+            pub const SIMD_THRESHOLD: usize = $simd_threshold;
+
+            /// Reconstructs a checksum from its value, splitting it back into `a` from the less
+            /// significant half and `b` from the more significant half.
+            ///
+            /// This is the inverse of [`value`](Self::value), so a checksum persisted as a single
+            /// integer can be resumed.
+            ///
+            /// ```
+            /// use fletcher_simd::Fletcher16;
+            ///
+            /// let mut resumed = Fletcher16::from_value(Fletcher16::from(&b"abc"[..]).value());
+            /// resumed.update_with_slice(b"defgh");
+            ///
+            /// assert_eq!(resumed.value(), 0xF824);
+            /// ```
+            pub fn from_value(value: $result_type) -> Self {
+                const SHIFT_SIZE: usize = core::mem::size_of::<$block_type>() * 8;
+
+                Self::with_initial_values(
+                    value as $block_type,
+                    (value >> SHIFT_SIZE) as $block_type,
+                )
+            }
+
+            /// Resets the checksum in place to the state reconstructed from its value, as
+            /// [`from_value`](Self::from_value) does, e.g. to reuse a pooled object.
+            ///
+            /// ```
+            /// use fletcher_simd::Fletcher16;
+            ///
+            /// let mut fletcher = Fletcher16::new();
+            /// fletcher.update_with_slice(b"unrelated");
+            ///
+            /// fletcher.reset_with_value(Fletcher16::from(&b"abc"[..]).value());
+            /// fletcher.update_with_slice(b"defgh");
+            ///
+            /// assert_eq!(fletcher.value(), 0xF824);
+            /// ```
+            #[inline]
+            pub fn reset_with_value(&mut self, value: $result_type) {
+                *self = Self::from_value(value);
+            }
+
+            /// Updates the checksum with a slice of data of type `T::BlockType`, using SIMD
+            /// vectors of `LANES` blocks instead of the default width.
+            ///
+            /// The default width fills a 256-bit vector. A narrower width can be faster on
+            /// microarchitectures that split wide vectors into multiple operations. The checksum
+            /// is identical for every supported `LANES`.
+            ///
+            /// ```
+            /// use fletcher_simd::Fletcher32;
+            ///
+            /// let data: Vec<u16> = (0..100).collect();
+            ///
+            /// let mut default_width = Fletcher32::new();
+            /// default_width.update_with_slice(&data);
+            ///
+            /// let mut narrow = Fletcher32::new();
+            /// narrow.update_with_slice_lanes::<8>(&data);
+            ///
+            /// assert_eq!(default_width.value(), narrow.value());
+            /// ```
+            #[inline]
+            pub fn update_with_slice_lanes<const LANES: usize>(&mut self, data: &[$block_type])
+            where
+                LaneCount<LANES>: SupportedLaneCount,
+            {
+                // Inputs shorter than a vector never reach the SIMD path, so skip straight to
+                // the scalar loop rather than splitting the slice.
+                if data.len() < LANES {
+                    (self.a, self.b) = update_fletcher_scalar(self.a, self.b, data.iter().copied());
+                    return;
+                }
+
+                // Only the part of the slice that is aligned to the vector type is loaded as
+                // vectors, so that every load is aligned. The unaligned ends are summed in order
+                // around it with the scalar implementation.
+                let (prefix, simd_slice, suffix) = data.as_simd::<LANES>();
+
+                (self.a, self.b) = update_fletcher_scalar(self.a, self.b, prefix.iter().copied());
+                if !simd_slice.is_empty() {
+                    (self.a, self.b) =
+                        update_fletcher_simd(self.a, self.b, simd_slice.iter().copied());
+                }
+                (self.a, self.b) = update_fletcher_scalar(self.a, self.b, suffix.iter().copied());
+            }
+
+            /// Updates the checksum with a slice of data of type `T::BlockType`, as with
+            /// [`update_with_slice`](Self::update_with_slice), but only using the SIMD
+            /// implementation for slices of at least `threshold` blocks instead of
+            /// [`SIMD_THRESHOLD`](Self::SIMD_THRESHOLD).
+            ///
+            /// The checksum is the same for any threshold. A threshold of 0 always uses SIMD for
+            /// inputs of at least one vector, and `usize::MAX` never uses it.
+            ///
+            /// ```
+            /// use fletcher_simd::Fletcher64;
+            ///
+            /// let data: Vec<u32> = (0..40).collect();
+            ///
+            /// let mut fletcher = Fletcher64::new();
+            /// fletcher.update_with_slice_threshold(&data, 64);
+            ///
+            /// let mut default_threshold = Fletcher64::new();
+            /// default_threshold.update_with_slice(&data);
+            ///
+            /// assert_eq!(fletcher, default_threshold);
+            /// ```
+            #[inline]
+            pub fn update_with_slice_threshold(&mut self, data: &[$block_type], threshold: usize) {
+                if data.len() < threshold {
+                    (self.a, self.b) = update_fletcher_scalar(self.a, self.b, data.iter().copied());
+                    return;
+                }
+
+                self.update_with_slice_lanes::<{ MAX_VEC_SIZE / $block_size }>(data);
             }
 
-            /// Constructs a new `Fletcher<T>` with specific values.
+            /// Updates the checksum with an array of data of type `T::BlockType`, whose length is
+            /// known at compile time, e.g. a fixed-size header or record.
+            ///
+            /// Whether `data` is shorter than [`SIMD_THRESHOLD`](Self::SIMD_THRESHOLD) is known at
+            /// compile time, so short arrays compile to just the scalar loop, which can be fully
+            /// unrolled, without the length check and SIMD setup of
+            /// [`update_with_slice`](Self::update_with_slice). Longer arrays take the same path as
+            /// a slice. The checksum is the same as that of the slice.
             ///
-            /// `a` will represent the lesser significant bits.
-            /// `b` will represent the more significant bits.
-            pub fn with_initial_values(a: $block_type, b: $block_type) -> Self {
-                Self { a, b }
+            /// ```
+            /// use fletcher_simd::Fletcher16;
+            ///
+            /// let mut fletcher = Fletcher16::new();
+            /// fletcher.update_array(b"abcde");
+            ///
+            /// assert_eq!(fletcher.value(), 0xC3EF);
+            /// ```
+            #[inline]
+            pub fn update_array<const N: usize>(&mut self, data: &[$block_type; N]) {
+                const NUM_LANES: usize = MAX_VEC_SIZE / $block_size;
+
+                if N < Self::SIMD_THRESHOLD {
+                    (self.a, self.b) = update_fletcher_scalar(self.a, self.b, data.iter().copied());
+                    return;
+                }
+
+                // Unaligned loads through an iterator measured about twice as slow as the
+                // aligned vectors of the slice path for a 64-byte `Fletcher16` record, so only
+                // the choice of the scalar loop is made at compile time.
+                self.update_with_slice_lanes::<NUM_LANES>(data);
             }
 
-            /// Updates the checksum with a slice of data of type `T::BlockType`.
-            pub fn update_with_slice(&mut self, data: &[$block_type]) {
-                if data.is_empty() {
+            /// Updates the checksum with a slice of bytes, interpreting each group of
+            /// `size_of::<T::BlockType>()` bytes as a block in the given byte order.
+            ///
+            /// Each vector of bytes is loaded once and grouped into blocks in the registers, with
+            /// big-endian blocks byte-swapped by a shuffle, as it is fed into the SIMD
+            /// accumulation, so no separate decoding pass over the input is needed. If the length
+            /// of `bytes` is not a multiple of the block size, the trailing partial block is
+            /// zero-padded, as if the input were followed by zero bytes.
+            ///
+            /// ```
+            /// use fletcher_simd::{Endianness, Fletcher32};
+            ///
+            /// let mut little = Fletcher32::new();
+            /// little.update_with_bytes(&[0x01, 0x02], Endianness::Little);
+            ///
+            /// let mut big = Fletcher32::new();
+            /// big.update_with_bytes(&[0x02, 0x01], Endianness::Big);
+            ///
+            /// assert_eq!(little, big);
+            /// assert_eq!(little.value(), 0x0201_0201);
+            /// ```
+            pub fn update_with_bytes(&mut self, bytes: &[u8], endianness: Endianness) {
+                if bytes.is_empty() {
                     return;
                 }
 
                 const NUM_LANES: usize = MAX_VEC_SIZE / $block_size;
 
-                let (simd_slice, remainder_slice) =
-                    data.split_at(data.len() - (data.len() % NUM_LANES));
+                let decode = |block: [u8; $block_size]| match endianness {
+                    Endianness::Little => <$block_type>::from_le_bytes(block),
+                    Endianness::Big => <$block_type>::from_be_bytes(block),
+                };
 
-                if !simd_slice.is_empty() {
+                let (simd_bytes, remainder_bytes) =
+                    bytes.split_at(bytes.len() - (bytes.len() % MAX_VEC_SIZE));
+
+                if !simd_bytes.is_empty() {
                     (self.a, self.b) = update_fletcher_simd(
                         self.a,
                         self.b,
-                        simd_slice
-                            .chunks(NUM_LANES)
-                            .map(|slice| Simd::<$block_type, NUM_LANES>::from_slice(slice)),
+                        simd_bytes.chunks_exact(MAX_VEC_SIZE).map(|chunk| {
+                            Simd::<$block_type, NUM_LANES>::from_bytes_vec(
+                                Simd::from_slice(chunk),
+                                endianness,
+                            )
+                        }),
                     );
                 }
 
-                if !remainder_slice.is_empty() {
-                    (self.a, self.b) =
-                        update_fletcher_scalar(self.a, self.b, remainder_slice.iter().copied());
+                if !remainder_bytes.is_empty() {
+                    (self.a, self.b) = update_fletcher_scalar(
+                        self.a,
+                        self.b,
+                        remainder_bytes.chunks($block_size).map(|chunk| {
+                            let mut block = [0u8; $block_size];
+                            block[..chunk.len()].copy_from_slice(chunk);
+                            decode(block)
+                        }),
+                    );
                 }
             }
 
+            /// Updates the checksum with a slice of data of type `T::BlockType`, reversing the bits
+            /// of each block before it is summed.
+            ///
+            /// This mirrors the reflected input ("refin") option of CRC parameterizations, for
+            /// formats that consume the bits of each block least significant first. It is not
+            /// part of Fletcher's checksum, and there is no single standard that uses it.
+            ///
+            /// ```
+            /// use fletcher_simd::Fletcher16;
+            ///
+            /// let mut reflected = Fletcher16::new();
+            /// reflected.update_with_slice_reflected(&[0b0000_0001, 0b1000_0000]);
+            ///
+            /// let mut plain = Fletcher16::new();
+            /// plain.update_with_slice(&[0b1000_0000, 0b0000_0001]);
+            ///
+            /// assert_eq!(reflected, plain);
+            /// ```
+            pub fn update_with_slice_reflected(&mut self, data: &[$block_type]) {
+                const NUM_LANES: usize = MAX_VEC_SIZE / $block_size;
+
+                let (prefix, simd_slice, suffix) = data.as_simd::<NUM_LANES>();
+
+                self.update_with_iter_scalar(prefix.iter().map(|elem| elem.reverse_bits()));
+                self.update_with_simd_chunks(
+                    simd_slice.iter().map(|vec| {
+                        Simd::from_array(vec.to_array().map(<$block_type>::reverse_bits))
+                    }),
+                );
+                self.update_with_iter_scalar(suffix.iter().map(|elem| elem.reverse_bits()));
+            }
+
+            /// Updates the checksum with a slice of signed blocks, e.g. a `c_char` buffer from C,
+            /// each of which is summed as the unsigned block with the same bit pattern.
+            ///
+            /// The blocks are converted with `as` a vector at a time, so this is as fast as
+            /// [`update_with_slice`](Self::update_with_slice) and needs no `unsafe` cast of the
+            /// slice.
+            ///
+            /// ```
+            /// use fletcher_simd::Fletcher16;
+            ///
+            /// let mut signed = Fletcher16::new();
+            /// signed.update_with_slice_signed(&[-1, 0x7F]);
+            ///
+            /// let mut unsigned = Fletcher16::new();
+            /// unsigned.update_with_slice(&[0xFF, 0x7F]);
+            ///
+            /// assert_eq!(signed, unsigned);
+            /// ```
+            pub fn update_with_slice_signed(&mut self, data: &[$signed_type]) {
+                const NUM_LANES: usize = MAX_VEC_SIZE / $block_size;
+
+                let (prefix, simd_slice, suffix) = data.as_simd::<NUM_LANES>();
+
+                self.update_with_iter_scalar(prefix.iter().map(|&elem| elem as $block_type));
+                self.update_with_simd_chunks(
+                    simd_slice.iter().map(|vec| vec.cast::<$block_type>()),
+                );
+                self.update_with_iter_scalar(suffix.iter().map(|&elem| elem as $block_type));
+            }
+
+            /// Updates the checksum with every `stride`-th block of `data`, starting from the
+            /// first: `data[0]`, `data[stride]`, `data[2 * stride]` and so on.
+            ///
+            /// The selected blocks are gathered into SIMD vectors, which uses gather instructions
+            /// on targets that have them. This is meant for spot checks of large buffers. The
+            /// result is the checksum of the selected blocks only, so it is not comparable to the
+            /// checksum of the whole of `data`: every block that is checked moves by a different
+            /// number of positions, which changes its weight in `b`.
+            ///
+            /// ```
+            /// use fletcher_simd::Fletcher16;
+            ///
+            /// let mut strided = Fletcher16::new();
+            /// strided.update_strided(b"aXbXc", 2);
+            ///
+            /// assert_eq!(strided, Fletcher16::from(&b"abc"[..]));
+            /// ```
+            ///
+            /// # Panics
+            ///
+            /// Panics if `stride` is zero.
+            pub fn update_strided(&mut self, data: &[$block_type], stride: usize) {
+                const NUM_LANES: usize = MAX_VEC_SIZE / $block_size;
+
+                assert!(stride != 0, "stride must be nonzero");
+
+                let num_blocks = match data.len() {
+                    0 => 0,
+                    len => (len - 1) / stride + 1,
+                };
+                let num_vecs = num_blocks / NUM_LANES;
+
+                if num_vecs > 0 {
+                    // Offsets of the lanes of a vector from the vector's first block. Only
+                    // computed once a whole vector is selected, so that they are all in bounds
+                    // and cannot overflow.
+                    let lane_offsets =
+                        Simd::<usize, NUM_LANES>::from_array(core::array::from_fn(|lane| {
+                            lane * stride
+                        }));
+
+                    self.update_with_simd_chunks((0..num_vecs).map(|vec| {
+                        let first = Simd::splat(vec * NUM_LANES * stride);
+                        Simd::<$block_type, NUM_LANES>::gather_or_default(
+                            data,
+                            first + lane_offsets,
+                        )
+                    }));
+                }
+                self.update_with_iter_scalar(
+                    data.iter()
+                        .step_by(stride)
+                        .skip(num_vecs * NUM_LANES)
+                        .copied(),
+                );
+            }
+
+            /// Updates the checksum with a slice of bytes holding blocks in memory, as if it were
+            /// a slice of `T::BlockType`.
+            ///
+            /// Blocks are read in [`Endianness::NATIVE`] byte order. With the `bytemuck` feature,
+            /// when `bytes` is aligned to the block type, the blocks are summed in place as with
+            /// [`update_with_slice`](Self::update_with_slice). Otherwise, they are decoded as
+            /// with [`update_with_bytes`](Self::update_with_bytes), which keeps the default build
+            /// free of unsafe code. Either way, a trailing partial block is zero-padded.
+            ///
+            /// ```
+            /// use fletcher_simd::Fletcher32;
+            ///
+            /// let blocks: [u16; 4] = [0x6261, 0x6463, 0x6665, 0x6867];
+            /// let bytes: Vec<u8> = blocks.iter().flat_map(|block| block.to_ne_bytes()).collect();
+            ///
+            /// let mut from_bytes = Fletcher32::new();
+            /// from_bytes.update_bytes_as_blocks(&bytes);
+            ///
+            /// let mut from_blocks = Fletcher32::new();
+            /// from_blocks.update_with_slice(&blocks);
+            ///
+            /// assert_eq!(from_bytes, from_blocks);
+            /// ```
+            pub fn update_bytes_as_blocks(&mut self, bytes: &[u8]) {
+                #[cfg(feature = "bytemuck")]
+                {
+                    let (prefix, blocks, suffix) = bytemuck::pod_align_to::<u8, $block_type>(bytes);
+
+                    if prefix.is_empty() {
+                        self.update_with_slice(blocks);
+                        self.update_with_bytes(suffix, Endianness::NATIVE);
+                        return;
+                    }
+                }
+
+                self.update_with_bytes(bytes, Endianness::NATIVE);
+            }
+
             /// Updates the checksum with an iterator over elements of type `T::BlockType`.
+            ///
+            /// Iterators that know their length up front, such as [`ExactSizeIterator`]s, are
+            /// gathered into vectors a whole vector at a time for as many blocks as
+            /// [`size_hint`](Iterator::size_hint) guarantees. Any other iterator, or whatever is
+            /// left after that, is buffered one block at a time. Either way, the checksum is the
+            /// same, even if the size hint is wrong.
             pub fn update_with_iter<Iter>(&mut self, elems: Iter)
             where
                 Iter: Iterator<Item = $block_type>,
             {
-                const NUM_LANES: usize = MAX_VEC_SIZE / $block_size;
+                self.update_with_iter_lanes::<Iter, { MAX_VEC_SIZE / $block_size }>(elems);
+            }
+
+            /// Updates the checksum with an iterator over elements of type `T::BlockType`, using
+            /// SIMD vectors of `LANES` blocks instead of the default width.
+            ///
+            /// See [`update_with_slice_lanes`](Self::update_with_slice_lanes) for details.
+            pub fn update_with_iter_lanes<Iter, const LANES: usize>(&mut self, elems: Iter)
+            where
+                Iter: Iterator<Item = $block_type>,
+                LaneCount<LANES>: SupportedLaneCount,
+            {
+                let mut elems = elems;
+
+                // Nothing to do for iterators known to be empty, as with an empty slice.
+                if elems.size_hint().1 == Some(0) {
+                    return;
+                }
+
+                // The lower bound of the size hint is exact for `ExactSizeIterator`s, so gather
+                // that many whole vectors without checking after every block whether a vector is
+                // full. The lower bound cannot be trusted, so an iterator that ends early leaves
+                // a partial vector, which is summed with the scalar implementation instead.
+                let mut sized_vecs = elems.size_hint().0 / LANES;
+                let mut partial = (Simd::<$block_type, LANES>::default(), 0);
+                if sized_vecs > 0 {
+                    let vecs = core::iter::from_fn(|| {
+                        if sized_vecs == 0 {
+                            return None;
+                        }
+                        sized_vecs -= 1;
+
+                        let mut len = 0;
+                        let vec = Simd::from_array(core::array::from_fn(|_| {
+                            elems.next().map_or(0, |elem| {
+                                len += 1;
+                                elem
+                            })
+                        }));
+
+                        if len == LANES {
+                            Some(vec)
+                        } else {
+                            (partial, sized_vecs) = ((vec, len), 0);
+                            None
+                        }
+                    });
+                    (self.a, self.b) = update_fletcher_simd(self.a, self.b, vecs);
 
-                let mut simd_vec = Simd::<$block_type, NUM_LANES>::default();
+                    let (vec, len) = partial;
+                    if len > 0 {
+                        (self.a, self.b) = update_fletcher_scalar(
+                            self.a,
+                            self.b,
+                            vec.as_array()[..len].iter().copied(),
+                        );
+                        return;
+                    }
+                }
+
+                let mut simd_vec = Simd::<$block_type, LANES>::default();
                 let mut simd_size = 0;
 
-                // Grab chunks of `NUM_LANES` and feed them into the SIMD calculation.
+                // Grab chunks of `LANES` and feed them into the SIMD calculation.
                 (self.a, self.b) = update_fletcher_simd(
                     self.a,
                     self.b,
@@ -141,7 +1667,7 @@ macro_rules! impl_fletcher {
                         simd_vec[simd_size] = elem;
                         simd_size += 1;
 
-                        if simd_size == NUM_LANES {
+                        if simd_size == LANES {
                             simd_size = 0;
                             Some(simd_vec)
                         } else {
@@ -150,7 +1676,7 @@ macro_rules! impl_fletcher {
                     }),
                 );
 
-                // If the number elements are not a multiple of `NUM_LANES`, use scalar fallback to
+                // If the number elements are not a multiple of `LANES`, use scalar fallback to
                 // compute remainder slice.
                 if simd_size > 0 {
                     (self.a, self.b) = update_fletcher_scalar(
@@ -161,35 +1687,103 @@ macro_rules! impl_fletcher {
                 }
             }
 
-            /// Updates the checksum with an iterator over elements of type `T::BlockType` using
-            /// a scalar-only implementation.
-            pub fn update_with_iter_scalar<Iter>(&mut self, elems: Iter)
+            /// Updates the checksum with an iterator over SIMD vectors of `LANES` blocks each, in
+            /// order, without going through a slice.
+            ///
+            /// Any supported lane count gives the same checksum as
+            /// [`update_with_slice`](Self::update_with_slice) over the same blocks. The default
+            /// width used elsewhere fills a 256-bit vector: 32 lanes for `Fletcher16`, 16 for
+            /// `Fletcher32`, 8 for `Fletcher64` and 4 for `Fletcher128`.
+            ///
+            /// ```
+            /// #![feature(portable_simd)]
+            /// use core::simd::Simd;
+            /// use fletcher_simd::Fletcher64;
+            ///
+            /// let vecs = [Simd::from_array([1u32; 8]), Simd::from_array([2u32; 8])];
+            ///
+            /// let mut from_vecs = Fletcher64::new();
+            /// from_vecs.update_with_simd_chunks(vecs.iter().copied());
+            ///
+            /// let mut from_slice = Fletcher64::new();
+            /// from_slice.update_with_slice(&[[1; 8], [2; 8]].concat());
+            ///
+            /// assert_eq!(from_vecs, from_slice);
+            /// ```
+            pub fn update_with_simd_chunks<Iter, const LANES: usize>(&mut self, vecs: Iter)
             where
-                Iter: Iterator<Item = $block_type>,
+                Iter: Iterator<Item = Simd<$block_type, LANES>>,
+                LaneCount<LANES>: SupportedLaneCount,
             {
-                (self.a, self.b) = update_fletcher_scalar(self.a, self.b, elems);
+                (self.a, self.b) = update_fletcher_simd(self.a, self.b, vecs);
             }
+        }
+    };
+}
 
-            /// Returns the checksum value.
-            pub fn value(&self) -> $result_type {
-                const SHIFT_SIZE: usize = core::mem::size_of::<$block_type>() * 8;
+impl_fletcher!(u16, u8, i8, 1, 32);
+impl_fletcher!(u32, u16, i16, 2, 16);
+impl_fletcher!(u64, u32, i32, 4, 16);
+impl_fletcher!(u128, u64, i64, 8, 48);
+impl_fletcher_common!(U256, u128, 16);
 
-                ((self.b as $result_type) << SHIFT_SIZE) | self.a as $result_type
-            }
-        }
+impl FletcherChecksum for U256 {
+    type BlockType = u128;
 
-        impl From<Fletcher<$result_type>> for $result_type {
-            fn from(f: Fletcher<$result_type>) -> Self {
-                f.value()
-            }
-        }
-    };
+    const BLOCK_BYTES: usize = 16;
+    const RESULT_BITS: u32 = U256::BITS;
+
+    #[inline]
+    fn from_components(a: u128, b: u128) -> Self {
+        U256::from_words(b, a)
+    }
 }
 
-impl_fletcher!(u16, u8, 1);
-impl_fletcher!(u32, u16, 2);
-impl_fletcher!(u64, u32, 4);
-impl_fletcher!(u128, u64, 8);
+/// There are no SIMD vectors of `u128` lanes on most targets, so the 256-bit checksum always uses
+/// the scalar implementation.
+impl Fletcher<U256> {
+    /// Reconstructs a checksum from its value, splitting it back into `a` from the less
+    /// significant half and `b` from the more significant half.
+    ///
+    /// This is the inverse of [`value`](Self::value), so a checksum persisted as a single integer
+    /// can be resumed.
+    pub fn from_value(value: U256) -> Self {
+        let (b, a) = value.into_words();
+        Self::with_initial_values(a, b)
+    }
+
+    /// Resets the checksum in place to the state reconstructed from its value, as
+    /// [`from_value`](Self::from_value) does, e.g. to reuse a pooled object.
+    #[inline]
+    pub fn reset_with_value(&mut self, value: U256) {
+        *self = Self::from_value(value);
+    }
+
+    /// Updates the checksum with a slice of bytes, interpreting each group of
+    /// `size_of::<T::BlockType>()` bytes as a block in the given byte order.
+    ///
+    /// If the length of `bytes` is not a multiple of the block size, the trailing partial block is
+    /// zero-padded, as if the input were followed by zero bytes.
+    pub fn update_with_bytes(&mut self, bytes: &[u8], endianness: Endianness) {
+        self.update_with_iter_scalar(bytes.chunks(16).map(|chunk| {
+            let mut block = [0u8; 16];
+            block[..chunk.len()].copy_from_slice(chunk);
+
+            match endianness {
+                Endianness::Little => u128::from_le_bytes(block),
+                Endianness::Big => u128::from_be_bytes(block),
+            }
+        }));
+    }
+
+    /// Updates the checksum with an iterator over elements of type `T::BlockType`.
+    pub fn update_with_iter<Iter>(&mut self, elems: Iter)
+    where
+        Iter: Iterator<Item = u128>,
+    {
+        self.update_with_iter_scalar(elems);
+    }
+}
 
 /// Convenient type alias for the 16-bit Fletcher checksum object.
 pub type Fletcher16 = Fletcher<u16>;
@@ -203,6 +1797,92 @@ pub type Fletcher64 = Fletcher<u64>;
 /// Convenient type alias for the 128-bit Fletcher checksum object.
 pub type Fletcher128 = Fletcher<u128>;
 
+/// Convenient type alias for the 256-bit Fletcher checksum object.
+pub type Fletcher256 = Fletcher<U256>;
+
+/// Checksums a byte slice in one expression.
+///
+/// ```
+/// use fletcher_simd::Fletcher16;
+///
+/// assert_eq!(Fletcher16::from(&b"abcdefgh"[..]).value(), 0xF824);
+/// ```
+impl From<&[u8]> for Fletcher16 {
+    fn from(data: &[u8]) -> Self {
+        let mut fletcher = Self::new();
+        fletcher.update_with_slice(data);
+        fletcher
+    }
+}
+
+/// Macro to implement checksumming a byte slice in one expression for checksums with blocks wider
+/// than a byte, which can fail.
+macro_rules! impl_try_from_bytes {
+    ($result_type:ty) => {
+        /// Checksums a byte slice in one expression.
+        ///
+        /// The conversion is strict: bytes are grouped into blocks in the byte order
+        /// [`Endianness::DEFAULT`], and a slice whose length is not a multiple of the block size is
+        /// rejected rather than zero-padded. Use [`update_with_bytes`](Fletcher::update_with_bytes)
+        /// to pad a trailing partial block instead.
+        impl TryFrom<&[u8]> for Fletcher<$result_type> {
+            type Error = BlockLengthError;
+
+            fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+                let block_bytes = <$result_type as FletcherChecksum>::BLOCK_BYTES;
+
+                if data.len() % block_bytes != 0 {
+                    return Err(BlockLengthError {
+                        len: data.len(),
+                        block_bytes,
+                    });
+                }
+
+                let mut fletcher = Self::new();
+                fletcher.update_with_bytes(data, Endianness::DEFAULT);
+                Ok(fletcher)
+            }
+        }
+    };
+}
+
+impl_try_from_bytes!(u32);
+impl_try_from_bytes!(u64);
+impl_try_from_bytes!(u128);
+impl_try_from_bytes!(U256);
+
+/// Error returned when converting a byte slice into a checksum whose length is not a multiple of
+/// the block size.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BlockLengthError {
+    len: usize,
+    block_bytes: usize,
+}
+
+impl BlockLengthError {
+    /// Returns the length of the rejected slice in bytes.
+    pub fn input_len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the block size in bytes that the length had to be a multiple of.
+    pub fn block_bytes(&self) -> usize {
+        self.block_bytes
+    }
+}
+
+impl core::fmt::Display for BlockLengthError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "length {} is not a multiple of the block size {}",
+            self.len, self.block_bytes
+        )
+    }
+}
+
+impl std::error::Error for BlockLengthError {}
+
 /// Private helper trait for making [`update_fletcher_simd`] generic.
 trait FletcherSimdVec<T, const LANES: usize>:
     Add<Self, Output = Self>
@@ -213,11 +1893,19 @@ trait FletcherSimdVec<T, const LANES: usize>:
     + Default
     + Mul<Self, Output = Self>
     + Sized
+    + Shl<Self, Output = Self>
     + SimdUint
+    + Sub<Self, Output = Self>
 where
     T: Copy + Clone + Default + SimdElement + WrappingAdd + WrappingSub,
     LaneCount<LANES>: SupportedLaneCount,
 {
+    /// Sums the lanes in the lane type, wrapping modulo `2^k` for `k`-bit lanes.
+    ///
+    /// This does not widen, and does not need to: wrapping addition and multiplication are
+    /// congruences modulo `2^k`, so lanes that overflowed before the reduction still reduce to the
+    /// same sum modulo `2^k` as the scalar implementation. Sums that must be exact are widened
+    /// before they are accumulated instead, see [`sum_widened`].
     fn horizontal_sum(self) -> T;
 }
 
@@ -241,19 +1929,45 @@ impl_simdvec!(u32);
 impl_simdvec!(u64);
 
 /// Function that updates a fletcher checksum using SIMD.
-#[multiversion]
-#[clone(target = "[x86|x86_64]+avx+avx2")]
-#[clone(target = "[x86|x86_64]+avx")]
-#[clone(target = "[x86|x86_64]+sse+sse2")]
-#[clone(target = "[x86|x86_64]+sse")]
-#[clone(target = "[arm|aarch64]+neon")]
+///
+/// All of the arithmetic, including the weighting of the lanes into `b`, wraps modulo `2^k` for a
+/// `k`-bit `BlockType`, so the sums are only correct modulo `2^k`. Rather than widening inside the
+/// kernel, checksums with another modulus widen the blocks before they get here and keep each run
+/// short enough that the exact sums fit in the wide type, as [`sum_widened`] does. The wrapped sums
+/// are then equal to the exact ones and can be reduced by any modulus.
+///
+/// Without widening, the lanes are only exact for a single vector at any width: after `n` vectors
+/// of blocks below `2^k`, a lane of `a` can reach `n * (2^k - 1)` and a lane of `b` can reach
+/// `(2^k - 1) * n * (n + 1) / 2`, and the lanes are then weighted by up to `LANES` before the
+/// reduction. Widening only the reduction would therefore not make longer runs exact, since the
+/// lanes have already wrapped by then. The wrapping checksums do not need them to be, and the
+/// run lengths of the other moduli are listed on [`ModularFletcher`].
+///
+/// With `runtime_dispatch`, the best clone is chosen on every call by `std`'s CPU feature
+/// detection, which caches its results internally. Without it, `multiversion` is not used at all
+/// and this is an ordinary function compiled for the enabled target features (e.g. with
+/// `-C target-cpu=native`), so there is no dispatch left on any call. A binary built without it
+/// only uses the instruction sets it was compiled for, even if the CPU it runs on supports more.
+///
+/// There is deliberately no clone for `wasm32`: WebAssembly validates a module's instructions
+/// up front, so `simd128` cannot be detected at runtime. Building with
+/// `-C target-feature=+simd128` enables it for the default implementation instead, which then
+/// runs on 128-bit vectors. Without it, the same code is lowered to scalar instructions.
+// Keep the clones in sync with `dispatched_target_features`.
+#[cfg_attr(feature = "runtime_dispatch", multiversion)]
+#[cfg_attr(feature = "runtime_dispatch", clone(target = "[x86|x86_64]+avx+avx2"))]
+#[cfg_attr(feature = "runtime_dispatch", clone(target = "[x86|x86_64]+avx"))]
+#[cfg_attr(feature = "runtime_dispatch", clone(target = "[x86|x86_64]+sse+sse2"))]
+#[cfg_attr(feature = "runtime_dispatch", clone(target = "[x86|x86_64]+sse"))]
+#[cfg_attr(feature = "runtime_dispatch", clone(target = "[arm|aarch64]+neon"))]
 fn update_fletcher_simd<BlockType, Iter, SimdVec, const LANES: usize>(
     mut a: BlockType,
     mut b: BlockType,
     elems: Iter,
 ) -> (BlockType, BlockType)
 where
-    BlockType: Copy
+    BlockType: 'static
+        + Copy
         + Clone
         + Default
         + TryFrom<usize>
@@ -263,37 +1977,122 @@ where
         + WrappingMul
         + WrappingSub,
     <BlockType as TryFrom<usize>>::Error: Debug,
+    usize: AsPrimitive<BlockType>,
     LaneCount<LANES>: SupportedLaneCount,
     Iter: Iterator<Item = SimdVec>,
     SimdVec: FletcherSimdVec<BlockType, LANES> + IndexMut<usize, Output = BlockType>,
 {
     let mut a_accum = SimdVec::default();
     let mut b_accum = SimdVec::default();
+    let mut num_vecs: usize = 0;
 
     for elem in elems {
         a_accum = a_accum + elem;
         b_accum = b_accum + a_accum;
+        num_vecs += 1;
     }
 
+    // b += (a * num_blocks), since the incoming `a` is added to `b` once per block.
+    let num_blocks: BlockType = num_vecs.wrapping_mul(LANES).as_();
+    b = b.wrapping_add(&a.wrapping_mul(&num_blocks));
+
     a = a.wrapping_add(&a_accum.horizontal_sum());
 
-    // b += (LANES * b_accum)
-    let b_prime = BlockType::wrapping_mul(
-        &BlockType::try_from(LANES).unwrap(),
-        &b_accum.horizontal_sum(),
+    // b += sum(LANES * b_accum[i] - i * a_accum[i]) for i in 0..LANES, weighted lane-wise so that
+    // both terms share a single reduction. `LANES` is always a power of two, so the first product
+    // is a shift, which is cheaper than a multiply for byte lanes.
+    let (lanes_shift, increasing_mask) = {
+        let mut lanes_shift = SimdVec::default();
+        let mut increasing_mask = SimdVec::default();
+        (0..LANES).for_each(|i| {
+            lanes_shift[i] = BlockType::try_from(LANES.trailing_zeros() as usize).unwrap();
+            increasing_mask[i] = BlockType::try_from(i).unwrap();
+        });
+        (lanes_shift, increasing_mask)
+    };
+    let b_prime = (b_accum << lanes_shift) - a_accum * increasing_mask;
+    b = b.wrapping_add(&b_prime.horizontal_sum());
+
+    (a, b)
+}
+
+/// Function that computes the exact sums of a run of blocks, widening each block to a larger lane
+/// type so that the sums can grow past the range of the block type.
+///
+/// The sums start from zero, so they are exact as long as the caller keeps the run short enough
+/// that the final `b` fits in `Wide`. Callers use this to back checksums that reduce by a modulus
+/// other than a power of two.
+fn sum_widened<Narrow, Wide, const LANES: usize>(data: &[Narrow]) -> (Wide, Wide)
+where
+    Narrow: SimdCast + Into<Wide>,
+    Wide: 'static
+        + Copy
+        + Clone
+        + Default
+        + TryFrom<usize>
+        + SimdCast
+        + Unsigned
+        + WrappingAdd
+        + WrappingMul
+        + WrappingSub,
+    <Wide as TryFrom<usize>>::Error: Debug,
+    usize: AsPrimitive<Wide>,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<Wide, LANES>: FletcherSimdVec<Wide, LANES> + IndexMut<usize, Output = Wide>,
+{
+    let (simd_slice, remainder_slice) = data.split_at(data.len() - (data.len() % LANES));
+
+    let (a, b) = update_fletcher_simd(
+        Wide::zero(),
+        Wide::zero(),
+        simd_slice
+            .chunks(LANES)
+            .map(|slice| Simd::<Narrow, LANES>::from_slice(slice).cast::<Wide>()),
     );
-    b = b.wrapping_add(&b_prime);
 
-    // b -= (i * a_accum[i]) for i in 0..LANES
-    let increasing_mask = {
-        let mut vec = SimdVec::default();
-        (0..LANES).for_each(|i| vec[i] = BlockType::try_from(i).unwrap());
-        vec
+    update_fletcher_scalar(a, b, remainder_slice.iter().map(|&elem| elem.into()))
+}
+
+/// Formats the big-endian unsigned integer `be_bytes` in `radix`, zero-padded to the number of
+/// digits of the largest integer of the same length. See [`Fletcher::format_radix`].
+fn format_be_bytes_radix(be_bytes: &[u8], radix: u32) -> String {
+    assert!(
+        (2..=36).contains(&radix),
+        "radix must be in the range 2 to 36, got {}",
+        radix
+    );
+
+    // Divides the integer by the radix in place, one byte at a time from the most significant,
+    // and returns the remainder, i.e. the least significant digit.
+    let div_rem = |num: &mut [u8]| {
+        num.iter_mut().fold(0, |rem, byte| {
+            let cur = (rem << 8) | u32::from(*byte);
+            *byte = (cur / radix) as u8;
+            cur % radix
+        })
     };
-    let sub_a = a_accum * increasing_mask;
-    b = b.wrapping_sub(&sub_a.horizontal_sum());
 
-    (a, b)
+    let mut max = vec![u8::MAX; be_bytes.len()];
+    let mut width = 0;
+    while max.iter().any(|&byte| byte != 0) {
+        div_rem(&mut max);
+        width += 1;
+    }
+
+    let mut num = be_bytes.to_vec();
+    let mut digits: Vec<char> = (0..width)
+        .map(|_| core::char::from_digit(div_rem(&mut num), radix).unwrap())
+        .collect();
+    digits.reverse();
+    digits.into_iter().collect()
+}
+
+/// The `SplitMix64` finalizer, a bijection on `u64` in which every output bit depends on every
+/// input bit.
+fn mix64(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
 }
 
 /// Fallback function that updates a fletcher checksum.
@@ -0,0 +1,11 @@
+//! Marker types for the arithmetic a [`Fletcher`](crate::Fletcher) checksum reduces its sums with.
+
+/// The sums wrap modulo `2^k`, where `k` is the block size in bits. This is the default mode of
+/// [`Fletcher`](crate::Fletcher), and the one that all of the SIMD paths implement.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Wrapping;
+
+/// The sums are reduced modulo `2^k - 1`, as in the classic definition of Fletcher's checksum.
+/// [`ModularFletcher<T>`](crate::ModularFletcher) is `Fletcher<T, Modular>`.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Modular;
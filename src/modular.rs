@@ -0,0 +1,242 @@
+//! Fletcher checksums that reduce by `2^k - 1` rather than wrapping modulo `2^k`.
+
+use {
+    crate::{
+        sum_widened, update_fletcher_scalar, Fletcher, FletcherChecksum, Modular, MAX_VEC_SIZE,
+    },
+    core::{
+        marker::PhantomData,
+        simd::{LaneCount, SupportedLaneCount},
+    },
+};
+
+/// A Fletcher checksum object that reduces both sums modulo `2^k - 1`, where `k` is the block
+/// size in bits, as in the classic definition of Fletcher's checksum.
+///
+/// This is [`Fletcher`] in the [`Modular`] mode, so it has the same layout, but only the methods
+/// listed here.
+///
+/// The sums are accumulated exactly in a type twice as wide as the block and reduced once per
+/// run of blocks, rather than once per block. Each run is summed from zero, so a run of `n`
+/// blocks stays exact as long as `(2^k - 1) * n * (n + 1) / 2` fits in the wide type. The run
+/// lengths are the largest `n` for which that holds:
+///
+/// | Width | Modulus        | Wide type | Run length      |
+/// |-------|----------------|-----------|-----------------|
+/// | 16    | `2^8 - 1`      | `u32`     | 5803            |
+/// | 32    | `2^16 - 1`     | `u32`     | 361             |
+/// | 64    | `2^32 - 1`     | `u64`     | 92681           |
+/// | 128   | `2^64 - 1`     | `u128`    | `u32::MAX`[^1]  |
+///
+/// [^1]: The exact limit is 6074000999 blocks, which does not fit in a 32-bit `usize`.
+///
+/// The runs are split within each update rather than across updates, so a single call over a
+/// slice of any length, such as a large memory map, is reduced as often as it needs to be.
+///
+/// The 16-, 32- and 64-bit runs are summed in SIMD lanes of the wide type. There are no SIMD
+/// vectors of `u128`, so the 128-bit checksum sums its runs with the scalar implementation.
+///
+/// The run lengths do not depend on the number of lanes, so wider vectors such as the 16 `u32`
+/// lanes of a 512-bit AVX-512 register need no more frequent reductions. Each lane accumulates
+/// every `L`-th block of the run, and the lanes are combined into `b` as the sum of
+/// `L * b_lane - i * a_lane`. All of this wraps modulo `2^w` for a `w`-bit wide type, so even if a
+/// lane's accumulator or an intermediate product wraps, the combined sums are congruent to the
+/// exact sums modulo `2^w`. The exact sums are less than `2^w` by the choice of run length, so
+/// they are equal. [`update_with_slice_lanes`](ModularFletcher::update_with_slice_lanes) sums
+/// with any supported number of lanes.
+///
+/// Reducing modulo `2^k - 1` leaves two representations of zero, `0` and `2^k - 1`.
+/// [`value`](Self::value) always uses `0`, as implementations that reduce with `%` do.
+/// Implementations that reduce with end-around carry, as in ones' complement addition, only
+/// produce `0` while every block has been zero, and `2^k - 1` for any other sum that is a
+/// multiple of the modulus. [`value_ones_complement`](Self::value_ones_complement) matches those.
+///
+/// # Examples
+///
+/// ```
+/// use fletcher_simd::ModularFletcher;
+///
+/// let mut fletcher = ModularFletcher::<u16>::new();
+/// fletcher.update_with_slice(b"abcdef");
+///
+/// assert_eq!(fletcher.value(), 0x2057);
+/// ```
+pub type ModularFletcher<T> = Fletcher<T, Modular>;
+
+impl<T: FletcherChecksum> Fletcher<T, Modular> {
+    /// Constructs a new `ModularFletcher<T>` starting from the sums `a` and `b`, e.g. to match a
+    /// reference implementation that starts from a nonzero seed.
+    ///
+    /// Both sums are reduced modulo `2^k - 1` along with the data, so a seed of `2^k - 1` is the
+    /// same as a seed of `0` for [`value`](ModularFletcher::value).
+    pub fn with_initial_values(a: T::BlockType, b: T::BlockType) -> Self {
+        Self {
+            a,
+            b,
+            mode: PhantomData,
+        }
+    }
+}
+
+macro_rules! impl_modular_fletcher {
+    ($result_type:ty, $block_type:ty, $wide_type:ty, $max_run_len:expr, $sum_run:expr) => {
+        impl Fletcher<$result_type, Modular> {
+            /// The modulus that both sums are reduced by.
+            const MODULUS: $wide_type = <$block_type>::MAX as $wide_type;
+
+            /// Maximum number of blocks that are summed before reducing.
+            const MAX_RUN_LEN: usize = $max_run_len;
+
+            /// Constructs a new `ModularFletcher<T>` with the default values.
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// Updates the checksum with a slice of data of type `T::BlockType`.
+            pub fn update_with_slice(&mut self, data: &[$block_type]) {
+                for run in data.chunks(Self::MAX_RUN_LEN) {
+                    let (run_a, run_b) = $sum_run(run);
+                    self.reduce_run(run_a, run_b, run.len());
+                }
+            }
+
+            /// Updates the checksum with an iterator over elements of type `T::BlockType` using
+            /// a scalar-only implementation.
+            pub fn update_with_iter_scalar<Iter>(&mut self, elems: Iter)
+            where
+                Iter: Iterator<Item = $block_type>,
+            {
+                let (mut run_a, mut run_b, mut run_len): ($wide_type, $wide_type, usize) =
+                    (0, 0, 0);
+
+                for elem in elems {
+                    run_a += elem as $wide_type;
+                    run_b += run_a;
+                    run_len += 1;
+
+                    if run_len == Self::MAX_RUN_LEN {
+                        self.reduce_run(run_a, run_b, run_len);
+                        (run_a, run_b, run_len) = (0, 0, 0);
+                    }
+                }
+
+                self.reduce_run(run_a, run_b, run_len);
+            }
+
+            /// Returns the checksum value, with both sums fully reduced to `0..2^k - 1`.
+            pub fn value(&self) -> $result_type {
+                Self::join(self.a % <$block_type>::MAX, self.b % <$block_type>::MAX)
+            }
+
+            /// Returns the checksum value in ones' complement form, where a sum that is a nonzero
+            /// multiple of the modulus is `2^k - 1` rather than `0`.
+            ///
+            /// This only differs from [`value`](Self::value) when a sum is a multiple of the
+            /// modulus, and matches implementations that reduce with end-around carry.
+            ///
+            /// ```
+            /// use fletcher_simd::ModularFletcher;
+            ///
+            /// let mut fletcher = ModularFletcher::<u16>::new();
+            /// fletcher.update_with_slice(&[0x01, 0xFE]);
+            ///
+            /// assert_eq!(fletcher.value(), 0x0100);
+            /// assert_eq!(fletcher.value_ones_complement(), 0x01FF);
+            /// ```
+            pub fn value_ones_complement(&self) -> $result_type {
+                Self::join(self.a, self.b)
+            }
+
+            fn join(a: $block_type, b: $block_type) -> $result_type {
+                const SHIFT_SIZE: usize = core::mem::size_of::<$block_type>() * 8;
+
+                ((b as $result_type) << SHIFT_SIZE) | a as $result_type
+            }
+
+            /// Reduces `x` to `1..=2^k - 1` if anything nonzero was added into it, or to `0`
+            /// otherwise, so that the reduced sums remember whether they were ever nonzero.
+            fn fold(x: $wide_type, nonzero: bool) -> $wide_type {
+                if nonzero {
+                    (x + Self::MODULUS - 1) % Self::MODULUS + 1
+                } else {
+                    0
+                }
+            }
+
+            /// Folds the exact sums of a run of `run_len` blocks, summed from zero, into the
+            /// reduced sums.
+            fn reduce_run(&mut self, run_a: $wide_type, run_b: $wide_type, run_len: usize) {
+                let a = self.a as $wide_type;
+                let b = self.b as $wide_type;
+
+                // Every term is reduced before adding, so neither the product nor the sums can
+                // overflow the wide type. Whether a sum is nonzero is decided from its terms
+                // rather than from the reduced sum, since `a` is added into `b` once per block,
+                // and a multiple of the modulus of those, e.g. from a seeded `a` followed by
+                // zeros, reduces to zero.
+                let shift = (run_len as $wide_type % Self::MODULUS) * a % Self::MODULUS;
+                let b_nonzero = b != 0 || run_b != 0 || (a != 0 && run_len != 0);
+
+                self.b = Self::fold(b + shift + run_b % Self::MODULUS, b_nonzero) as $block_type;
+                self.a = Self::fold(a + run_a % Self::MODULUS, a != 0 || run_a != 0) as $block_type;
+            }
+        }
+
+        impl From<ModularFletcher<$result_type>> for $result_type {
+            fn from(f: ModularFletcher<$result_type>) -> Self {
+                f.value()
+            }
+        }
+    };
+}
+
+/// Adds `update_with_slice_lanes` to the widths whose runs are summed in SIMD lanes.
+macro_rules! impl_modular_fletcher_lanes {
+    ($result_type:ty, $block_type:ty, $wide_type:ty) => {
+        impl Fletcher<$result_type, Modular> {
+            /// Updates the checksum with a slice of data of type `T::BlockType`, summing each run
+            /// in SIMD vectors of `LANES` lanes of the wide type instead of the default width.
+            ///
+            /// The checksum is identical for every supported `LANES`, and the runs are just as
+            /// long, as explained in the [type documentation](ModularFletcher).
+            pub fn update_with_slice_lanes<const LANES: usize>(&mut self, data: &[$block_type])
+            where
+                LaneCount<LANES>: SupportedLaneCount,
+            {
+                for run in data.chunks(Self::MAX_RUN_LEN) {
+                    let (run_a, run_b) = sum_widened::<$block_type, $wide_type, LANES>(run);
+                    self.reduce_run(run_a, run_b, run.len());
+                }
+            }
+        }
+    };
+}
+
+impl_modular_fletcher!(
+    u16,
+    u8,
+    u32,
+    5803,
+    sum_widened::<u8, u32, { MAX_VEC_SIZE / 4 }>
+);
+impl_modular_fletcher!(
+    u32,
+    u16,
+    u32,
+    361,
+    sum_widened::<u16, u32, { MAX_VEC_SIZE / 4 }>
+);
+impl_modular_fletcher!(
+    u64,
+    u32,
+    u64,
+    92681,
+    sum_widened::<u32, u64, { MAX_VEC_SIZE / 8 }>
+);
+impl_modular_fletcher!(u128, u64, u128, u32::MAX as usize, |run: &[u64]| {
+    update_fletcher_scalar(0, 0, run.iter().map(|&elem| elem as u128))
+});
+
+impl_modular_fletcher_lanes!(u16, u8, u32);
+impl_modular_fletcher_lanes!(u32, u16, u32);
+impl_modular_fletcher_lanes!(u64, u32, u64);
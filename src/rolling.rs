@@ -0,0 +1,78 @@
+//! Rolling Fletcher checksums over a sliding window.
+
+use crate::{Fletcher, FletcherChecksum, U256};
+
+/// A Fletcher checksum of a fixed-size window that can slide over a stream in O(1) per step.
+///
+/// This is useful for rsync-style matching and content-defined chunking, where the checksum of
+/// every window position is needed.
+///
+/// # Examples
+///
+/// ```
+/// use fletcher_simd::{Fletcher16, RollingFletcher};
+///
+/// const DATA: &[u8] = b"abcdefgh";
+/// const WINDOW_LEN: usize = 4;
+///
+/// let mut rolling = RollingFletcher::<u16>::new(&DATA[..WINDOW_LEN]);
+/// rolling.roll(DATA[0], DATA[WINDOW_LEN], WINDOW_LEN);
+///
+/// let mut fletcher = Fletcher16::new();
+/// fletcher.update_with_slice(&DATA[1..WINDOW_LEN + 1]);
+///
+/// assert_eq!(rolling.value(), fletcher.value());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RollingFletcher<T: FletcherChecksum> {
+    fletcher: Fletcher<T>,
+}
+
+macro_rules! impl_rolling_fletcher {
+    ($result_type:ty, $block_type:ty) => {
+        impl RollingFletcher<$result_type> {
+            /// Constructs a new `RollingFletcher<T>` over the initial window.
+            pub fn new(window: &[$block_type]) -> Self {
+                let mut fletcher = Fletcher::<$result_type>::new();
+                fletcher.update_with_slice(window);
+
+                Self { fletcher }
+            }
+
+            /// Slides the window forward by one element, removing `out_elem` from the front and
+            /// appending `in_elem` to the back.
+            ///
+            /// `window_len` is the number of elements in the window. Removing `out_elem` takes
+            /// away a weight of `window_len` from `b`, since it had been added to `b` once for
+            /// each element in the window.
+            pub fn roll(&mut self, out_elem: $block_type, in_elem: $block_type, window_len: usize) {
+                // Dropping `out_elem` from the front is the same as appending the window to a
+                // checksum with `a = -out_elem`, which carries `-out_elem` into `b` once for each
+                // element in the window.
+                let mut rest =
+                    Fletcher::<$result_type>::with_initial_values(out_elem.wrapping_neg(), 0);
+                rest.combine_reduced(&self.fletcher, window_len as $block_type);
+
+                self.fletcher = rest;
+                self.fletcher
+                    .update_with_iter_scalar(core::iter::once(in_elem));
+            }
+
+            /// Returns the checksum of the current window.
+            pub fn fletcher(&self) -> Fletcher<$result_type> {
+                self.fletcher
+            }
+
+            /// Returns the checksum value of the current window.
+            pub fn value(&self) -> $result_type {
+                self.fletcher.value()
+            }
+        }
+    };
+}
+
+impl_rolling_fletcher!(u16, u8);
+impl_rolling_fletcher!(u32, u16);
+impl_rolling_fletcher!(u64, u32);
+impl_rolling_fletcher!(u128, u64);
+impl_rolling_fletcher!(U256, u128);
@@ -0,0 +1,25 @@
+//! One-shot checksums of a slice starting from nonzero sums.
+
+use crate::{Fletcher, U256};
+
+macro_rules! impl_seeded {
+    ($name:ident, $result_type:ty, $block_type:ty, $alias:literal) => {
+        #[doc = concat!("Computes the ", $alias, " checksum of `data` starting from the sums `a0` and `b0`.")]
+        ///
+        /// This is a shorthand for constructing the checksum with
+        #[doc = concat!("[`", $alias, "::with_initial_values(a0, b0)`](crate::Fletcher::with_initial_values),")]
+        /// updating it with `data` and taking its value, e.g. to match a reference implementation
+        /// that starts from a nonzero seed.
+        pub fn $name(data: &[$block_type], a0: $block_type, b0: $block_type) -> $result_type {
+            let mut fletcher = Fletcher::<$result_type>::with_initial_values(a0, b0);
+            fletcher.update_with_slice(data);
+            fletcher.value()
+        }
+    };
+}
+
+impl_seeded!(fletcher16_seeded, u16, u8, "Fletcher16");
+impl_seeded!(fletcher32_seeded, u32, u16, "Fletcher32");
+impl_seeded!(fletcher64_seeded, u64, u32, "Fletcher64");
+impl_seeded!(fletcher128_seeded, u128, u64, "Fletcher128");
+impl_seeded!(fletcher256_seeded, U256, u128, "Fletcher256");
@@ -0,0 +1,92 @@
+//! Fletcher checksums that several threads can update through a shared reference.
+
+use {
+    crate::{Fletcher, FletcherChecksum},
+    std::sync::{Mutex, MutexGuard, PoisonError},
+};
+
+/// A Fletcher checksum object behind a [`Mutex`], so that it can be updated through `&self`, e.g.
+/// from several threads appending to the same log.
+///
+/// Each update is applied whole while the lock is held, so the result is always the checksum of
+/// the updates concatenated in the order in which they took the lock. Only `a` is independent of
+/// that order. A block's weight in `b` is its distance from the end, so the final value is only
+/// deterministic if the order of the updates is controlled by the caller, or if it does not
+/// matter, e.g. because the updated slices are all equal.
+///
+/// There is no lock-free variant with an atomic fetch-add for each sum. Appending `n` blocks adds
+/// `n * a` to `b`, using the value of `a` from before the append, so the two sums cannot be
+/// updated independently: another thread's append in between would be weighted wrongly, and
+/// the sums would no longer be the checksum of any order of the updates.
+///
+/// The lock is only held for the summing itself. A panic while it is held cannot leave the sums
+/// torn, since they are only written once the update is complete, so a poisoned lock is ignored.
+///
+/// # Examples
+///
+/// ```
+/// use fletcher_simd::{Fletcher16, SharedFletcher};
+///
+/// let shared = SharedFletcher::<u16>::new();
+///
+/// std::thread::scope(|scope| {
+///     for _ in 0..4 {
+///         scope.spawn(|| shared.update_with_slice(b"abc"));
+///     }
+/// });
+///
+/// let mut fletcher = Fletcher16::new();
+/// fletcher.update_with_slice(b"abcabcabcabc");
+///
+/// assert_eq!(shared.value(), fletcher.value());
+/// ```
+#[derive(Debug, Default)]
+pub struct SharedFletcher<T: FletcherChecksum> {
+    fletcher: Mutex<Fletcher<T>>,
+}
+
+impl<T: FletcherChecksum> SharedFletcher<T> {
+    /// Constructs a new `SharedFletcher<T>` with the default values.
+    pub fn new() -> Self {
+        Self::from(Fletcher::<T>::new())
+    }
+
+    /// Updates the checksum with a slice of data of type `T::BlockType`, as a single append that
+    /// no other update is interleaved with.
+    pub fn update_with_slice(&self, data: &[T::BlockType]) {
+        let mut fletcher = self.lock();
+        let mut updated = *fletcher;
+        updated.update_with_slice(data);
+        *fletcher = updated;
+    }
+
+    /// Returns a copy of the underlying checksum.
+    pub fn fletcher(&self) -> Fletcher<T> {
+        *self.lock()
+    }
+
+    /// Returns the checksum value.
+    #[must_use]
+    pub fn value(&self) -> T {
+        self.lock().value()
+    }
+
+    /// Consumes the shared checksum and returns the underlying checksum.
+    pub fn into_inner(self) -> Fletcher<T> {
+        self.fletcher
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Fletcher<T>> {
+        self.fletcher.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: FletcherChecksum> From<Fletcher<T>> for SharedFletcher<T> {
+    fn from(fletcher: Fletcher<T>) -> Self {
+        Self {
+            fletcher: Mutex::new(fletcher),
+        }
+    }
+}
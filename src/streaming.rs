@@ -0,0 +1,159 @@
+//! Fletcher checksums of byte streams that carry a partial block between updates.
+
+use crate::{Endianness, Fletcher, FletcherChecksum, U256};
+
+/// Size in bytes of the widest block, that of [`Fletcher256`](crate::Fletcher256).
+const MAX_BLOCK_BYTES: usize = 16;
+
+/// A Fletcher checksum object that is fed bytes in chunks of any length, holding back a trailing
+/// partial block until the next chunk completes it.
+///
+/// [`Fletcher::update_with_bytes`] zero-pads every chunk that does not end on a block boundary,
+/// so splitting the input changes the checksum. This keeps the partial block in a fixed array
+/// in the object instead, without allocating, so the checksum is the same however the input is
+/// split. Only [`finalize`](Self::finalize) zero-pads the last partial block.
+///
+/// # Examples
+///
+/// ```
+/// use fletcher_simd::{Endianness, Fletcher32, StreamingFletcher};
+///
+/// let mut streaming = StreamingFletcher::<u32>::new(Endianness::Little);
+/// streaming.update_bytes(b"abc");
+/// streaming.update_bytes(b"defgh");
+///
+/// let mut fletcher = Fletcher32::new();
+/// fletcher.update_with_bytes(b"abcdefgh", Endianness::Little);
+///
+/// assert_eq!(streaming.finalize(), fletcher.value());
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct StreamingFletcher<T: FletcherChecksum> {
+    fletcher: Fletcher<T>,
+    endianness: Endianness,
+    // Sized for the widest block, so only the first block's worth is used by narrower ones.
+    partial: [u8; MAX_BLOCK_BYTES],
+    partial_len: usize,
+}
+
+/// Overwrites both sums and the held-back bytes with zeroes, which leaves the checksum in the
+/// state returned by [`new`](StreamingFletcher::new). The byte order is kept.
+#[cfg(feature = "zeroize")]
+impl<T> zeroize::Zeroize for StreamingFletcher<T>
+where
+    T: FletcherChecksum,
+    Fletcher<T>: zeroize::Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.fletcher.zeroize();
+        self.partial.zeroize();
+        self.partial_len.zeroize();
+    }
+}
+
+macro_rules! impl_streaming_fletcher {
+    ($result_type:ty) => {
+        impl StreamingFletcher<$result_type> {
+            /// Constructs a new `StreamingFletcher<T>` with the default values, grouping bytes
+            /// into blocks in the given byte order.
+            pub fn new(endianness: Endianness) -> Self {
+                Self {
+                    fletcher: Fletcher::<$result_type>::new(),
+                    endianness,
+                    partial: [0; MAX_BLOCK_BYTES],
+                    partial_len: 0,
+                }
+            }
+
+            /// Updates the checksum with a chunk of bytes, holding back a trailing partial
+            /// block for the next chunk to complete.
+            pub fn update_bytes(&mut self, mut bytes: &[u8]) {
+                let block_bytes = <$result_type as FletcherChecksum>::BLOCK_BYTES;
+
+                if self.partial_len > 0 {
+                    let fill = (block_bytes - self.partial_len).min(bytes.len());
+                    self.partial[self.partial_len..self.partial_len + fill]
+                        .copy_from_slice(&bytes[..fill]);
+                    self.partial_len += fill;
+                    bytes = &bytes[fill..];
+
+                    if self.partial_len < block_bytes {
+                        return;
+                    }
+                    self.fletcher
+                        .update_with_bytes(&self.partial[..block_bytes], self.endianness);
+                    self.partial_len = 0;
+                }
+
+                let whole = bytes.len() - bytes.len() % block_bytes;
+                self.fletcher
+                    .update_with_bytes(&bytes[..whole], self.endianness);
+
+                let remainder = &bytes[whole..];
+                self.partial[..remainder.len()].copy_from_slice(remainder);
+                self.partial_len = remainder.len();
+            }
+
+            /// Resets the checksum in place to the state reconstructed from its value by
+            /// [`Fletcher::from_value`], discarding any held-back partial block. The byte order
+            /// is kept.
+            pub fn reset_with_value(&mut self, value: $result_type) {
+                self.fletcher.reset_with_value(value);
+                self.partial = [0; MAX_BLOCK_BYTES];
+                self.partial_len = 0;
+            }
+
+            /// Returns the checksum value, with any held-back partial block zero-padded.
+            #[must_use]
+            pub fn finalize(self) -> $result_type {
+                self.into_fletcher().value()
+            }
+
+            /// Returns the underlying checksum, with any held-back partial block zero-padded.
+            fn into_fletcher(self) -> Fletcher<$result_type> {
+                let mut fletcher = self.fletcher;
+                fletcher.update_with_bytes(&self.partial[..self.partial_len], self.endianness);
+                fletcher
+            }
+        }
+
+        impl Fletcher<$result_type> {
+            /// Updates the checksum with the concatenation of several slices of bytes, e.g. the
+            /// buffers of a vectored write, grouped into blocks in the given byte order.
+            ///
+            /// Blocks that straddle two slices are joined back together, so this is the same as
+            /// [`update_with_bytes`](Self::update_with_bytes) over the concatenated bytes, with
+            /// only the final partial block zero-padded. Each slice is still summed with SIMD.
+            ///
+            /// ```
+            /// use fletcher_simd::{Endianness, Fletcher32};
+            ///
+            /// let mut vectored = Fletcher32::new();
+            /// vectored.update_vectored(&[b"abc", b"de", b"fgh"], Endianness::Little);
+            ///
+            /// let mut whole = Fletcher32::new();
+            /// whole.update_with_bytes(b"abcdefgh", Endianness::Little);
+            ///
+            /// assert_eq!(vectored, whole);
+            /// ```
+            pub fn update_vectored(&mut self, bufs: &[&[u8]], endianness: Endianness) {
+                let mut streaming = StreamingFletcher {
+                    fletcher: *self,
+                    endianness,
+                    partial: [0; MAX_BLOCK_BYTES],
+                    partial_len: 0,
+                };
+                for buf in bufs {
+                    streaming.update_bytes(buf);
+                }
+                *self = streaming.into_fletcher();
+            }
+        }
+    };
+}
+
+impl_streaming_fletcher!(u16);
+impl_streaming_fletcher!(u32);
+impl_streaming_fletcher!(u64);
+impl_streaming_fletcher!(u128);
+impl_streaming_fletcher!(U256);
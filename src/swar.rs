@@ -0,0 +1,70 @@
+//! A fallback that sums several blocks at once in the lanes of a `u64`, without hardware SIMD.
+//!
+//! This is only implemented for byte blocks. With eight lanes per word it is about twice as fast
+//! as the scalar loop, but with the four or two lanes of wider blocks the masking costs as much
+//! as it saves.
+
+use crate::{update_fletcher_scalar, Fletcher};
+
+macro_rules! impl_swar {
+    ($result_type:ty, $block_type:ty, $high_bits:expr) => {
+        impl Fletcher<$result_type> {
+            /// Updates the checksum with a slice of data of type `T::BlockType`, packing several
+            /// blocks into each `u64` and summing them lane-wise with plain integer instructions.
+            ///
+            /// The result is identical to [`update_with_slice`](Self::update_with_slice). This
+            /// is meant for targets where `std::simd` is lowered to scalar instructions, e.g.
+            /// `wasm32` without `simd128`. Where hardware SIMD is available,
+            /// [`update_with_slice`](Self::update_with_slice) is faster.
+            pub fn update_with_slice_swar(&mut self, data: &[$block_type]) {
+                const BITS: usize = <$block_type>::BITS as usize;
+                const LANES: usize = 64 / BITS;
+                /// Mask of the most significant bit of every lane.
+                const HIGH_BITS: u64 = $high_bits;
+
+                /// Adds every lane of `y` to the same lane of `x`, wrapping within the lane.
+                ///
+                /// The high bit of each lane is masked off before adding so that no carry can
+                /// cross into the next lane, then recovered with an XOR.
+                #[inline(always)]
+                fn lane_add(x: u64, y: u64) -> u64 {
+                    ((x & !HIGH_BITS) + (y & !HIGH_BITS)) ^ ((x ^ y) & HIGH_BITS)
+                }
+
+                let mut chunks = data.chunks_exact(LANES);
+                let mut a_accum: u64 = 0;
+                let mut b_accum: u64 = 0;
+
+                for chunk in &mut chunks {
+                    let word = chunk
+                        .iter()
+                        .enumerate()
+                        .fold(0, |word, (i, &elem)| word | (elem as u64) << (i * BITS));
+
+                    a_accum = lane_add(a_accum, word);
+                    b_accum = lane_add(b_accum, a_accum);
+                }
+
+                // Fold the lanes in the same way as `update_fletcher_simd`.
+                let num_blocks = (data.len() - chunks.remainder().len()) as $block_type;
+                self.b = self.b.wrapping_add(self.a.wrapping_mul(num_blocks));
+
+                for i in 0..LANES {
+                    let a_lane = (a_accum >> (i * BITS)) as $block_type;
+                    let b_lane = (b_accum >> (i * BITS)) as $block_type;
+
+                    self.a = self.a.wrapping_add(a_lane);
+                    self.b = self.b.wrapping_add(
+                        (b_lane.wrapping_mul(LANES as $block_type))
+                            .wrapping_sub(a_lane.wrapping_mul(i as $block_type)),
+                    );
+                }
+
+                (self.a, self.b) =
+                    update_fletcher_scalar(self.a, self.b, chunks.remainder().iter().copied());
+            }
+        }
+    };
+}
+
+impl_swar!(u16, u8, 0x8080_8080_8080_8080);
@@ -0,0 +1,79 @@
+//! A minimal 256-bit unsigned integer for the result of [`Fletcher256`](crate::Fletcher256).
+
+use core::convert::TryInto;
+
+/// A 256-bit unsigned integer, stored as two 128-bit words.
+///
+/// This only provides what is needed to hold and export a 256-bit checksum. It is not a
+/// general-purpose big integer type.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct U256 {
+    // Declared high word first so that the derived ordering is numeric.
+    high: u128,
+    low: u128,
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for U256 {
+    fn zeroize(&mut self) {
+        self.high.zeroize();
+        self.low.zeroize();
+    }
+}
+
+impl U256 {
+    /// The size of this integer type in bits.
+    pub const BITS: u32 = 256;
+
+    /// Constructs a `U256` from its more and less significant 128-bit words.
+    pub const fn from_words(high: u128, low: u128) -> Self {
+        Self { high, low }
+    }
+
+    /// Returns the more and less significant 128-bit words, in that order.
+    pub const fn into_words(self) -> (u128, u128) {
+        (self.high, self.low)
+    }
+
+    /// Returns the memory representation of this integer as a byte array in little-endian byte
+    /// order.
+    pub fn to_le_bytes(self) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        bytes[..16].copy_from_slice(&self.low.to_le_bytes());
+        bytes[16..].copy_from_slice(&self.high.to_le_bytes());
+        bytes
+    }
+
+    /// Returns the memory representation of this integer as a byte array in big-endian byte
+    /// order.
+    pub fn to_be_bytes(self) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        bytes[..16].copy_from_slice(&self.high.to_be_bytes());
+        bytes[16..].copy_from_slice(&self.low.to_be_bytes());
+        bytes
+    }
+
+    /// Creates an integer from its memory representation as a byte array in little-endian byte
+    /// order.
+    pub fn from_le_bytes(bytes: [u8; 32]) -> Self {
+        Self::from_words(
+            u128::from_le_bytes(bytes[16..].try_into().unwrap()),
+            u128::from_le_bytes(bytes[..16].try_into().unwrap()),
+        )
+    }
+
+    /// Creates an integer from its memory representation as a byte array in big-endian byte
+    /// order.
+    pub fn from_be_bytes(bytes: [u8; 32]) -> Self {
+        Self::from_words(
+            u128::from_be_bytes(bytes[..16].try_into().unwrap()),
+            u128::from_be_bytes(bytes[16..].try_into().unwrap()),
+        )
+    }
+}
+
+impl From<u128> for U256 {
+    fn from(low: u128) -> Self {
+        Self::from_words(0, low)
+    }
+}
@@ -0,0 +1,74 @@
+use fletcher_simd::{Fletcher128, Fletcher16, Fletcher32, Fletcher64};
+use proptest::{collection::vec, prelude::*};
+
+macro_rules! prop_fletcher {
+    ($mod_name:ident, $fletcher:ty, $block_type:ty) => {
+        mod $mod_name {
+            use super::*;
+
+            proptest! {
+                #[test]
+                fn simd_same_as_scalar(
+                    data in vec(any::<$block_type>(), 0..4096),
+                    a in any::<$block_type>(),
+                    b in any::<$block_type>(),
+                ) {
+                    let mut simd = <$fletcher>::with_initial_values(a, b);
+                    let mut scalar = <$fletcher>::with_initial_values(a, b);
+
+                    simd.update_with_slice(&data);
+                    scalar.update_with_iter_scalar(data.iter().copied());
+
+                    prop_assert_eq!(simd.value(), scalar.value());
+                }
+
+                #[test]
+                fn iter_same_as_scalar(data in vec(any::<$block_type>(), 0..4096)) {
+                    let mut simd = <$fletcher>::new();
+                    let mut scalar = <$fletcher>::new();
+
+                    simd.update_with_iter(data.iter().copied());
+                    scalar.update_with_iter_scalar(data.iter().copied());
+
+                    prop_assert_eq!(simd.value(), scalar.value());
+                }
+
+                #[test]
+                fn chunked_same_as_single_shot(
+                    data in vec(any::<$block_type>(), 0..4096),
+                    chunk_len in 1usize..256,
+                ) {
+                    let mut single_shot = <$fletcher>::new();
+                    let mut chunked = <$fletcher>::new();
+
+                    single_shot.update_with_slice(&data);
+                    for chunk in data.chunks(chunk_len) {
+                        chunked.update_with_slice(chunk);
+                    }
+
+                    prop_assert_eq!(single_shot.value(), chunked.value());
+                }
+            }
+
+            proptest! {
+                #![proptest_config(ProptestConfig::with_cases(8))]
+
+                #[test]
+                fn large_simd_same_as_scalar(data in vec(any::<$block_type>(), 0..(1 << 20))) {
+                    let mut simd = <$fletcher>::new();
+                    let mut scalar = <$fletcher>::new();
+
+                    simd.update_with_slice(&data);
+                    scalar.update_with_iter_scalar(data.iter().copied());
+
+                    prop_assert_eq!(simd.value(), scalar.value());
+                }
+            }
+        }
+    };
+}
+
+prop_fletcher!(fletcher16, Fletcher16, u8);
+prop_fletcher!(fletcher32, Fletcher32, u16);
+prop_fletcher!(fletcher64, Fletcher64, u32);
+prop_fletcher!(fletcher128, Fletcher128, u64);
@@ -1,5 +1,19 @@
-use byteorder::{ByteOrder, LittleEndian};
-use fletcher_simd::{Fletcher128, Fletcher16, Fletcher32, Fletcher64};
+#![feature(portable_simd)]
+
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use core::{convert::TryFrom, simd::Simd};
+use fletcher_simd::{
+    batch_fletcher128, batch_fletcher16, batch_fletcher32, batch_fletcher64, const_fletcher128,
+    const_fletcher16, const_fletcher256, const_fletcher32, const_fletcher64,
+    fletcher128_contribution, fletcher128_file, fletcher128_seeded, fletcher16_contribution,
+    fletcher16_file, fletcher16_seeded, fletcher256_contribution, fletcher256_file,
+    fletcher256_seeded, fletcher32_contribution, fletcher32_file, fletcher32_seeded,
+    fletcher64_contribution, fletcher64_file, fletcher64_seeded, Adler32, BufferedFletcher,
+    CheckedFletcher, Checksum, CountingFletcher, Endianness, Fletcher, Fletcher128, Fletcher16,
+    Fletcher256, Fletcher32, Fletcher64, FletcherAny, FletcherBuilder, FletcherChecksum,
+    FletcherIteratorExt, LanesFletcher, Modular, ModularFletcher, RollingFletcher, SharedFletcher,
+    StreamingFletcher, Wrapping, U256,
+};
 use rand::{
     distributions::{Distribution, Uniform},
     Rng,
@@ -29,6 +43,7 @@ fn simple_fletcher16() {
 }
 
 #[test]
+#[allow(clippy::redundant_closure)]
 fn simple_fletcher32() {
     const DATA: &str = "abcdefgh";
     let mut fletcher = Fletcher32::new();
@@ -43,6 +58,7 @@ fn simple_fletcher32() {
 }
 
 #[test]
+#[allow(clippy::redundant_closure)]
 fn simple_fletcher64() {
     const DATA: &str = "abcdefgh";
     let mut fletcher = Fletcher64::new();
@@ -57,6 +73,7 @@ fn simple_fletcher64() {
 }
 
 #[test]
+#[allow(clippy::redundant_closure)]
 fn simple_fletcher128() {
     const DATA: &str = "abcdefgh";
     let mut fletcher = Fletcher128::new();
@@ -71,6 +88,7 @@ fn simple_fletcher128() {
 }
 
 #[test]
+#[allow(clippy::redundant_static_lifetimes)]
 fn lorem_fletcher16() {
     const DATA: &'static str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Interdum velit laoreet id donec ultrices tincidunt. Phasellus vestibulum lorem sed risus ultricies tristique nulla aliquet. Id cursus metus aliquam eleifend mi in. Condimentum vitae sapien pellentesque habitant morbi tristique. Fringilla est ullamcorper eget nulla facilisi etiam dignissim diam quis.";
 
@@ -82,6 +100,7 @@ fn lorem_fletcher16() {
 }
 
 #[test]
+#[allow(clippy::redundant_closure, clippy::redundant_static_lifetimes)]
 fn lorem_fletcher32() {
     const DATA: &'static str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Interdum velit laoreet id donec ultrices tincidunt. Phasellus vestibulum lorem sed risus ultricies tristique nulla aliquet. Id cursus metus aliquam eleifend mi in. Condimentum vitae sapien pellentesque habitant morbi tristique. Fringilla est ullamcorper eget nulla facilisi etiam dignissim diam quis.";
 
@@ -97,6 +116,7 @@ fn lorem_fletcher32() {
 }
 
 #[test]
+#[allow(clippy::redundant_closure, clippy::redundant_static_lifetimes)]
 fn lorem_fletcher64() {
     const DATA: &'static str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Interdum velit laoreet id donec ultrices tincidunt. Phasellus vestibulum lorem sed risus ultricies tristique nulla aliquet. Id cursus metus aliquam eleifend mi in. Condimentum vitae sapien pellentesque habitant morbi tristique. Fringilla est ullamcorper eget nulla facilisi etiam dignissim diam quis.";
 
@@ -112,6 +132,7 @@ fn lorem_fletcher64() {
 }
 
 #[test]
+#[allow(clippy::redundant_closure, clippy::redundant_static_lifetimes)]
 fn lorem_fletcher128() {
     const DATA: &'static str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Interdum velit laoreet id donec ultrices tincidunt. Phasellus vestibulum lorem sed risus ultricies tristique nulla aliquet. Id cursus metus aliquam eleifend mi in. Condimentum vitae sapien pellentesque habitant morbi tristique. Fringilla est ullamcorper eget nulla facilisi etiam dignissim diam quis.";
 
@@ -126,27 +147,2839 @@ fn lorem_fletcher128() {
     assert_eq!(fletcher.value(), 0xC6B64C7008FC4EC12C654FCFBC31506C);
 }
 
+// Every length from empty through three vectors and a tail, for every width. The default
+// `update_with_slice` sums inputs below `SIMD_THRESHOLD` as scalars, which covers most of these
+// lengths for the wider blocks, so the SIMD kernel is also called directly at the default width.
 #[test]
 fn simd_scalar_same() {
     let mut rng = rand::thread_rng();
-    let size_range = Uniform::from(1..65);
+
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty) => {{
+            const LANES: usize = <$fletcher>::LANES;
+
+            // One block more than the longest input, so that it can also start one block in and be
+            // misaligned with the vectors.
+            let data: Vec<$block_type> = (0..3 * LANES + 6).map(|_| rng.gen()).collect();
+            let seed = <$fletcher>::with_initial_values(rng.gen(), rng.gen());
+
+            for offset in 0..2 {
+                for len in 0..=3 * LANES + 5 {
+                    let data = &data[offset..offset + len];
+
+                    let mut scalar = seed;
+                    scalar.update_with_iter_scalar(data.iter().copied());
+
+                    let mut simd = seed;
+                    simd.update_with_slice(data);
+                    assert_eq!(simd, scalar, "update_with_slice mismatch on: {:?}", data);
+
+                    let mut simd = seed;
+                    simd.update_with_slice_lanes::<LANES>(data);
+                    assert_eq!(
+                        simd, scalar,
+                        "update_with_slice_lanes mismatch on: {:?}",
+                        data
+                    );
+
+                    let mut simd = seed;
+                    simd.update_with_iter(data.iter().copied());
+                    assert_eq!(simd, scalar, "update_with_iter mismatch on: {:?}", data);
+                }
+            }
+        }};
+    }
+
+    check!(Fletcher16, u8);
+    check!(Fletcher32, u16);
+    check!(Fletcher64, u32);
+    check!(Fletcher128, u64);
+}
+
+#[test]
+fn bytes_same_as_byteorder() {
+    let mut rng = rand::thread_rng();
+    let size_range = Uniform::from(0..257);
 
     const NUM_ITERS: usize = 1000;
     for _ in 0..NUM_ITERS {
-        let mut simd = Fletcher64::new();
-        let mut scalar = Fletcher64::new();
-
         let size: usize = size_range.sample(&mut rng);
-        let data: Vec<u32> = (0..size).map(|_| rng.gen()).collect();
+        let data: Vec<u8> = (0..size).map(|_| rng.gen()).collect();
+
+        let mut from_bytes = Fletcher16::new();
+        let mut from_blocks = Fletcher16::new();
+        from_bytes.update_with_slice_le(&data);
+        from_blocks.update_with_slice(&data);
+        assert_eq!(
+            from_bytes.value(),
+            from_blocks.value(),
+            "mismatch on: {:?}",
+            data
+        );
+
+        // Zero-pad the trailing partial block so the `byteorder` reads line up.
+        let mut padded = data.clone();
+        padded.resize((size + 7) / 8 * 8, 0);
+
+        let mut from_bytes = Fletcher32::new();
+        let mut from_blocks = Fletcher32::new();
+        from_bytes.update_with_slice_le(&data);
+        from_blocks.update_with_iter(
+            padded[..(size + 1) / 2 * 2]
+                .chunks(2)
+                .map(LittleEndian::read_u16),
+        );
+        assert_eq!(
+            from_bytes.value(),
+            from_blocks.value(),
+            "mismatch on: {:?}",
+            data
+        );
+
+        let mut from_bytes = Fletcher64::new();
+        let mut from_blocks = Fletcher64::new();
+        from_bytes.update_with_slice_le(&data);
+        from_blocks.update_with_iter(
+            padded[..(size + 3) / 4 * 4]
+                .chunks(4)
+                .map(LittleEndian::read_u32),
+        );
+        assert_eq!(
+            from_bytes.value(),
+            from_blocks.value(),
+            "mismatch on: {:?}",
+            data
+        );
+
+        let mut from_bytes = Fletcher128::new();
+        let mut from_blocks = Fletcher128::new();
+        from_bytes.update_with_slice_le(&data);
+        from_blocks.update_with_iter(padded.chunks(8).map(LittleEndian::read_u64));
+        assert_eq!(
+            from_bytes.value(),
+            from_blocks.value(),
+            "mismatch on: {:?}",
+            data
+        );
+    }
+}
+
+#[test]
+fn bytes_be_same_as_byteorder() {
+    // Not a multiple of the vector size, so the scalar remainder is decoded too.
+    let data: Vec<u8> = (0..248).rev().collect();
+
+    let mut from_bytes = Fletcher32::new();
+    let mut from_blocks = Fletcher32::new();
+    from_bytes.update_with_slice_be(&data);
+    from_blocks.update_with_iter(data.chunks(2).map(BigEndian::read_u16));
+    assert_eq!(from_bytes.value(), from_blocks.value());
+
+    let mut from_bytes = Fletcher64::new();
+    let mut from_blocks = Fletcher64::new();
+    from_bytes.update_with_bytes(&data, Endianness::Big);
+    from_blocks.update_with_iter(data.chunks(4).map(BigEndian::read_u32));
+    assert_eq!(from_bytes.value(), from_blocks.value());
+
+    let mut from_bytes = Fletcher128::new();
+    let mut from_blocks = Fletcher128::new();
+    from_bytes.update_with_slice_be(&data);
+    from_blocks.update_with_iter(data.chunks(8).map(BigEndian::read_u64));
+    assert_eq!(from_bytes.value(), from_blocks.value());
+
+    // The byte order only matters for blocks wider than a byte.
+    let mut little = Fletcher16::new();
+    let mut big = Fletcher16::new();
+    little.update_with_slice_le(&data);
+    big.update_with_slice_be(&data);
+    assert_eq!(little, big);
+}
+
+#[test]
+fn fletcher256_words_and_bytes() {
+    let mut fletcher = Fletcher256::new();
+    fletcher.update_with_slice(&[u128::MAX, 2]);
+
+    // a = 2^128 - 1 + 2 wraps to 1, and b = (2^128 - 1) + 1 wraps to 0.
+    let value = fletcher.value();
+    assert_eq!(value.into_words(), (0, 1));
+    assert_eq!(value, U256::from(1));
+    assert_eq!(value.to_le_bytes()[0], 1);
+    assert_eq!(value.to_be_bytes()[31], 1);
+
+    let updated = fletcher.update([3]).value();
+    assert_eq!(updated, U256::from_words(4, 4));
+    assert!(updated > value);
+    assert_eq!(Fletcher256::BLOCK_BYTES, 16);
+    assert_eq!(Fletcher256::RESULT_BITS, 256);
+}
+
+#[test]
+fn modular_known_vectors() {
+    for (data, expected16, expected32, expected64) in [
+        ("abcde", 0xC8F0, 0xF04FC729, 0xC8C6C527646362C6),
+        ("abcdef", 0x2057, 0x56502D2A, 0xC8C72B276463C8C6),
+        ("abcdefgh", 0x0627, 0xEBE19591, 0x312E2B28CCCAC8C6),
+    ] {
+        // Zero-pad the trailing partial block so the `byteorder` reads line up.
+        let mut padded = data.as_bytes().to_vec();
+        padded.resize((data.len() + 3) / 4 * 4, 0);
+
+        let mut fletcher16 = ModularFletcher::<u16>::new();
+        fletcher16.update_with_slice(data.as_bytes());
+        assert_eq!(fletcher16.value(), expected16, "Fletcher-16 of {}", data);
+
+        let mut fletcher32 = ModularFletcher::<u32>::new();
+        fletcher32.update_with_slice(
+            &padded[..(data.len() + 1) / 2 * 2]
+                .chunks(2)
+                .map(LittleEndian::read_u16)
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(fletcher32.value(), expected32, "Fletcher-32 of {}", data);
+
+        let mut fletcher64 = ModularFletcher::<u64>::new();
+        fletcher64.update_with_slice(
+            &padded
+                .chunks(4)
+                .map(LittleEndian::read_u32)
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(fletcher64.value(), expected64, "Fletcher-64 of {}", data);
+    }
+}
+
+#[test]
+fn modes_known_values() {
+    for (data, wrapping, modular) in [("abcde", 0xC3EF, 0xC8F0), ("abcdefgh", 0xF824, 0x0627)] {
+        let mut fletcher = Fletcher::<u16, Wrapping>::new();
+        fletcher.update_with_slice(data.as_bytes());
+        assert_eq!(
+            fletcher.value(),
+            wrapping,
+            "wrapping Fletcher-16 of {}",
+            data
+        );
+
+        let mut fletcher = Fletcher::<u16, Modular>::new();
+        fletcher.update_with_slice(data.as_bytes());
+        assert_eq!(fletcher.value(), modular, "modular Fletcher-16 of {}", data);
+    }
+
+    // The mode is a zero-sized marker, so it does not change the layout.
+    let _: Fletcher16 = Fletcher::<u16, Wrapping>::new();
+    let _: ModularFletcher<u32> = Fletcher::<u32, Modular>::new();
+    assert_eq!(
+        core::mem::size_of::<Fletcher<u32, Modular>>(),
+        core::mem::size_of::<Fletcher32>()
+    );
+}
+
+// Saturated blocks overflow every lane of the SIMD accumulators many times over before they are
+// reduced, which must not change the wrapped sums.
+#[test]
+fn saturated_long_run_same_as_scalar() {
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty) => {
+            let data = vec![<$block_type>::MAX; 1 << 16];
+
+            let mut simd = <$fletcher>::new();
+            simd.update_with_slice(&data);
+
+            let mut scalar = <$fletcher>::new();
+            scalar.update_with_iter_scalar(data.iter().copied());
+
+            assert_eq!(simd, scalar, "{}", stringify!($fletcher));
+        };
+    }
+
+    check!(Fletcher16, u8);
+    check!(Fletcher32, u16);
+    check!(Fletcher64, u32);
+    check!(Fletcher128, u64);
+}
+
+#[test]
+fn signed_same_as_unsigned() {
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($fletcher:ty, $signed_type:ty, $block_type:ty) => {
+            for len in [0, 1, 7, 64, 1000] {
+                let data: Vec<$signed_type> = (0..len).map(|_| rng.gen()).collect();
+                let unsigned: Vec<$block_type> =
+                    data.iter().map(|&elem| elem as $block_type).collect();
+
+                // Offset the slices so that the unaligned prefix is exercised too.
+                for offset in 0..len.min(2) {
+                    let mut signed = <$fletcher>::new();
+                    signed.update_with_slice_signed(&data[offset..]);
+
+                    let mut expected = <$fletcher>::new();
+                    expected.update_with_slice(&unsigned[offset..]);
+
+                    assert_eq!(
+                        signed,
+                        expected,
+                        "{} of length {}",
+                        stringify!($fletcher),
+                        len
+                    );
+                }
+            }
+        };
+    }
+
+    check!(Fletcher16, i8, u8);
+    check!(Fletcher32, i16, u16);
+    check!(Fletcher64, i32, u32);
+    check!(Fletcher128, i64, u64);
+}
+
+/// Reduces after every block, which cannot overflow but is slow.
+fn modular_reference(data: impl Iterator<Item = u128>, modulus: u128) -> (u128, u128) {
+    data.fold((0, 0), |(a, b), elem| {
+        let a = (a + elem) % modulus;
+        (a, (b + a) % modulus)
+    })
+}
+
+// All-ones input maximizes the sums, so several times the run length overflows any
+// implementation that does not reduce often enough.
+#[test]
+fn modular_long_input_same_as_reference() {
+    let data = vec![u8::MAX; 5803 * 3 + 1];
+    let (a, b) = modular_reference(data.iter().map(|&x| x.into()), 0xFF);
+    let mut simd = ModularFletcher::<u16>::new();
+    let mut scalar = ModularFletcher::<u16>::new();
+    simd.update_with_slice(&data);
+    scalar.update_with_iter_scalar(data.iter().copied());
+    assert_eq!(simd.value(), (b << 8 | a) as u16);
+    assert_eq!(scalar.value(), simd.value());
+
+    let data = vec![u16::MAX - 1; 361 * 3 + 1];
+    let (a, b) = modular_reference(data.iter().map(|&x| x.into()), 0xFFFF);
+    let mut simd = ModularFletcher::<u32>::new();
+    let mut scalar = ModularFletcher::<u32>::new();
+    simd.update_with_slice(&data);
+    scalar.update_with_iter_scalar(data.iter().copied());
+    assert_eq!(simd.value(), (b << 16 | a) as u32);
+    assert_eq!(scalar.value(), simd.value());
+
+    let data = vec![u32::MAX - 1; 92681 * 3 + 1];
+    let (a, b) = modular_reference(data.iter().map(|&x| x.into()), 0xFFFF_FFFF);
+    let mut simd = ModularFletcher::<u64>::new();
+    let mut scalar = ModularFletcher::<u64>::new();
+    simd.update_with_slice(&data);
+    scalar.update_with_iter_scalar(data.iter().copied());
+    assert_eq!(simd.value(), (b << 32 | a) as u64);
+    assert_eq!(scalar.value(), simd.value());
+
+    // The 128-bit run length is too long to cross in a test, so only check the reduction.
+    let data = vec![u64::MAX - 1; 1000];
+    let (a, b) = modular_reference(data.iter().map(|&x| x.into()), u64::MAX.into());
+    let mut simd = ModularFletcher::<u128>::new();
+    let mut scalar = ModularFletcher::<u128>::new();
+    simd.update_with_slice(&data);
+    scalar.update_with_iter_scalar(data.iter().copied());
+    assert_eq!(simd.value(), b << 64 | a);
+    assert_eq!(scalar.value(), simd.value());
+}
+
+// 512-bit vectors hold 16 `u32` or 8 `u64` lanes, twice the default. The runs are just as long,
+// so crossing several of them with all-ones input would overflow if the lanes needed shorter ones.
+// A single call over hundreds of runs of a repeated block, whose exact sums have a closed form:
+// `a = c * n` and `b = c * n * (n + 1) / 2`.
+#[test]
+fn modular_repeated_block_single_call() {
+    macro_rules! check {
+        ($result_type:ty, $block_type:ty, $len:expr) => {
+            let block = <$block_type>::MAX - 2;
+            let modulus = <$block_type>::MAX as u128;
+            let len = $len as u128;
+            let a = block as u128 * len % modulus;
+            let b = block as u128 * (len * (len + 1) / 2 % modulus) % modulus;
+            let expected = (b << <$block_type>::BITS | a) as $result_type;
+
+            let mut scalar = ModularFletcher::<$result_type>::new();
+            scalar.update_with_iter_scalar(core::iter::repeat(block).take($len));
+            assert_eq!(
+                scalar.value(),
+                expected,
+                "scalar {}",
+                stringify!($result_type)
+            );
+
+            let data: Vec<$block_type> = core::iter::repeat(block).take($len).collect();
+            let mut simd = ModularFletcher::<$result_type>::new();
+            simd.update_with_slice(&data);
+            assert_eq!(simd.value(), expected, "SIMD {}", stringify!($result_type));
+        };
+    }
+
+    check!(u16, u8, 1 << 22);
+    check!(u32, u16, 1 << 20);
+    check!(u64, u32, 1 << 24);
+}
 
-        simd.update_with_slice(data.as_slice());
-        scalar.update_with_slice(data.as_slice());
+#[test]
+fn modular_wide_vectors_same_as_default() {
+    let data = vec![u8::MAX; 5803 * 3 + 1];
+    let mut default_width = ModularFletcher::<u16>::new();
+    default_width.update_with_slice(&data);
+    let mut wide = ModularFletcher::<u16>::new();
+    wide.update_with_slice_lanes::<16>(&data);
+    assert_eq!(wide, default_width);
+    let mut widest = ModularFletcher::<u16>::new();
+    widest.update_with_slice_lanes::<64>(&data);
+    assert_eq!(widest, default_width);
+
+    let data = vec![u16::MAX - 1; 361 * 3 + 1];
+    let mut default_width = ModularFletcher::<u32>::new();
+    default_width.update_with_slice(&data);
+    let mut wide = ModularFletcher::<u32>::new();
+    wide.update_with_slice_lanes::<16>(&data);
+    assert_eq!(wide, default_width);
+
+    let data = vec![u32::MAX - 1; 92681 * 3 + 1];
+    let mut default_width = ModularFletcher::<u64>::new();
+    default_width.update_with_slice(&data);
+    let mut wide = ModularFletcher::<u64>::new();
+    wide.update_with_slice_lanes::<8>(&data);
+    assert_eq!(wide, default_width);
+}
 
+/// Reduces with end-around carry after every block, as ones' complement implementations do,
+/// starting from the sums `seed`.
+fn ones_complement_reference(
+    seed: (u128, u128),
+    data: impl Iterator<Item = u128>,
+    bits: u32,
+) -> (u128, u128) {
+    let fold = |x: u128| (x & ((1 << bits) - 1)) + (x >> bits);
+    data.fold(seed, |(a, b), elem| {
+        let a = fold(a + elem);
+        (a, fold(b + a))
+    })
+}
+
+#[test]
+fn modular_ones_complement_same_as_reference() {
+    // The sums of all-zero input stay zero, and `0xFF` makes each sum pass through `0xFF`.
+    for (data, expected, expected_ones_complement) in [
+        (&[][..], 0x0000, 0x0000),
+        (&[0x00, 0x00][..], 0x0000, 0x0000),
+        (&[0xFF][..], 0x0000, 0xFFFF),
+        (&[0x01, 0xFE][..], 0x0100, 0x01FF),
+        (&b"abcdef"[..], 0x2057, 0x2057),
+        // The worked example of the Wikipedia article "Fletcher's checksum": the data bytes
+        // 0x01 0x02 checksum to 0x0403, and appending their check bytes 0xF8 0x04 makes both sums
+        // a nonzero multiple of 255, which a receiver verifies. The two folds differ on it.
+        (&[0x01, 0x02][..], 0x0403, 0x0403),
+        (&[0x01, 0x02, 0xF8, 0x04][..], 0x0000, 0xFFFF),
+    ] {
+        let mut fletcher = ModularFletcher::<u16>::new();
+        fletcher.update_with_slice(data);
+        assert_eq!(fletcher.value(), expected, "{:02X?}", data);
         assert_eq!(
-            simd.value(),
-            scalar.value(),
-            "mismatch on checksum from: {:?}",
+            fletcher.value_ones_complement(),
+            expected_ones_complement,
+            "{:02X?}",
             data
         );
     }
+
+    // The check bytes of the article follow from its formulas, f0 = 0xFF - ((c0 + c1) mod 0xFF)
+    // and f1 = 0xFF - ((c0 + f0) mod 0xFF), with c0 = 0x03 and c1 = 0x04 the sums of 0x01 0x02.
+    let (c0, c1) = (0x03, 0x04);
+    let f0 = 0xFF - ((c0 + c1) % 0xFF);
+    let f1 = 0xFF - ((c0 + f0) % 0xFF);
+    assert_eq!((f0, f1), (0xF8, 0x04));
+
+    // Cross several runs with sums that keep landing on multiples of the modulus.
+    let data: Vec<u8> = (0..5803 * 3 + 1)
+        .map(|i: u32| if i % 7 == 0 { 0x01 } else { 0xFF })
+        .collect();
+    let (a, b) = ones_complement_reference((0, 0), data.iter().map(|&x| x.into()), 8);
+    let mut simd = ModularFletcher::<u16>::new();
+    let mut scalar = ModularFletcher::<u16>::new();
+    simd.update_with_slice(&data);
+    scalar.update_with_iter_scalar(data.iter().copied());
+    assert_eq!(simd.value_ones_complement(), (b << 8 | a) as u16);
+    assert_eq!(scalar.value_ones_complement(), simd.value_ones_complement());
+
+    let data = vec![u16::MAX; 361 * 3 + 1];
+    let (a, b) = ones_complement_reference((0, 0), data.iter().map(|&x| x.into()), 16);
+    let mut simd = ModularFletcher::<u32>::new();
+    simd.update_with_slice(&data);
+    assert_eq!(simd.value_ones_complement(), (b << 16 | a) as u32);
+    assert_eq!(simd.value(), 0);
+
+    // A seeded `a` is added into `b` once per block, so a multiple of 255 zeros adds a multiple
+    // of the modulus to `b` that is still nonzero.
+    for &(seed, len) in &[
+        ((1, 0), 255),
+        ((1, 0), 5803 + 255),
+        ((0xFF, 0), 1),
+        ((3, 0xFF), 85),
+    ] {
+        let data = vec![0u8; len];
+        let (a, b) = ones_complement_reference(seed, data.iter().map(|&x| x.into()), 8);
+        let mut simd = ModularFletcher::<u16>::with_initial_values(seed.0 as u8, seed.1 as u8);
+        let mut scalar = simd;
+        simd.update_with_slice(&data);
+        scalar.update_with_iter_scalar(data.iter().copied());
+        assert_eq!(
+            simd.value_ones_complement(),
+            (b << 8 | a) as u16,
+            "{:?} {}",
+            seed,
+            len
+        );
+        assert_eq!(scalar.value_ones_complement(), simd.value_ones_complement());
+    }
+}
+
+#[test]
+fn unupdate_reverses_update() {
+    let data: Vec<u32> = (0..100).map(|i| i * 0x0102_0304).collect();
+    let trailer = [u32::MAX, 0, 42];
+
+    let mut expected = Fletcher64::with_initial_values(3, 5);
+    expected.update_with_slice(&data);
+
+    let mut fletcher = expected;
+    fletcher.update_with_slice(&trailer);
+    assert_ne!(fletcher, expected);
+
+    for &elem in trailer.iter().rev() {
+        fletcher.unupdate_last(elem);
+    }
+    assert_eq!(fletcher, expected);
+}
+
+#[test]
+fn bytes_as_blocks_aligned_and_misaligned() {
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty) => {
+            let block_bytes = <$fletcher>::BLOCK_BYTES;
+
+            for len in 0..=64 {
+                // Back the bytes with blocks so that offset 0 is aligned.
+                let backing: Vec<$block_type> =
+                    (0..len / block_bytes + 2).map(|_| rng.gen()).collect();
+                let backing_bytes: Vec<u8> = backing
+                    .iter()
+                    .flat_map(|block| block.to_ne_bytes())
+                    .collect();
+
+                for offset in 0..block_bytes {
+                    let bytes = unsafe {
+                        core::slice::from_raw_parts(backing.as_ptr().cast::<u8>().add(offset), len)
+                    };
+                    assert_eq!(bytes, &backing_bytes[offset..offset + len]);
+
+                    let mut as_blocks = <$fletcher>::new();
+                    as_blocks.update_bytes_as_blocks(bytes);
+
+                    let mut decoded = <$fletcher>::new();
+                    decoded.update_with_bytes(bytes, Endianness::NATIVE);
+
+                    assert_eq!(
+                        as_blocks, decoded,
+                        "mismatch at offset {} on: {:?}",
+                        offset, bytes
+                    );
+                }
+            }
+        };
+    }
+
+    check!(Fletcher16, u8);
+    check!(Fletcher32, u16);
+    check!(Fletcher64, u32);
+    check!(Fletcher128, u64);
+}
+
+#[test]
+fn seeded_same_as_initial_values() {
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($seeded:ident, $fletcher:ty, $block_type:ty) => {
+            let data: Vec<$block_type> = (0..100).map(|_| rng.gen()).collect();
+            let (a0, b0) = (rng.gen(), rng.gen());
+
+            let mut fletcher = <$fletcher>::with_initial_values(a0, b0);
+            fletcher.update_with_slice(&data);
+
+            assert_eq!($seeded(&data, a0, b0), fletcher.value());
+        };
+    }
+
+    check!(fletcher16_seeded, Fletcher16, u8);
+    check!(fletcher32_seeded, Fletcher32, u16);
+    check!(fletcher64_seeded, Fletcher64, u32);
+    check!(fletcher128_seeded, Fletcher128, u64);
+    check!(fletcher256_seeded, Fletcher256, u128);
+
+    assert_eq!(fletcher16_seeded(b"", 0x12, 0x34), 0x3412);
+}
+
+#[test]
+fn sum_same_as_slice() {
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty) => {
+            let data: Vec<$block_type> = (0..300).map(|_| rng.gen()).collect();
+
+            let mut expected = <$fletcher>::new();
+            expected.update_with_slice(&data);
+
+            assert_eq!(data.iter().copied().sum::<$fletcher>(), expected);
+            assert_eq!(data.iter().sum::<$fletcher>(), expected);
+        };
+    }
+
+    check!(Fletcher16, u8);
+    check!(Fletcher32, u16);
+    check!(Fletcher64, u32);
+    check!(Fletcher128, u64);
+    check!(Fletcher256, u128);
+
+    assert_eq!(
+        core::iter::empty::<u8>().sum::<Fletcher16>(),
+        Fletcher16::new()
+    );
+}
+
+#[test]
+fn marks_same_as_prefixes() {
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty) => {
+            let prior: Vec<$block_type> = (0..5).map(|_| rng.gen()).collect();
+            let data: Vec<$block_type> = (0..200).map(|_| rng.gen()).collect();
+
+            for mark_every in [1, 7, 32, 200, 201] {
+                let mut fletcher = <$fletcher>::new();
+                fletcher.update_with_slice(&prior);
+                let marks = fletcher.update_with_marks(&data, mark_every);
+                assert_eq!(marks.len(), data.len() / mark_every);
+
+                // Each mark covers the earlier update too.
+                for (i, &mark) in marks.iter().enumerate() {
+                    let mut prefix = <$fletcher>::new();
+                    prefix.update_with_iter_scalar(
+                        prior.iter().chain(&data[..(i + 1) * mark_every]).copied(),
+                    );
+                    assert_eq!(
+                        mark,
+                        prefix.value(),
+                        "mismatch at mark {} of {}",
+                        i,
+                        mark_every
+                    );
+                }
+
+                let mut whole = <$fletcher>::new();
+                whole.update_with_slice(&prior);
+                whole.update_with_slice(&data);
+                assert_eq!(fletcher, whole);
+            }
+        };
+    }
+
+    check!(Fletcher16, u8);
+    check!(Fletcher32, u16);
+    check!(Fletcher64, u32);
+    check!(Fletcher128, u64);
+    check!(Fletcher256, u128);
+}
+
+#[test]
+#[should_panic(expected = "mark_every must be nonzero")]
+fn marks_reject_zero_interval() {
+    let _ = Fletcher16::new().update_with_marks(b"abc", 0);
+}
+
+#[test]
+fn contribution_applied_same_as_update() {
+    let mut rng = rand::thread_rng();
+    let size_range = Uniform::from(0..300);
+
+    macro_rules! check {
+        ($contribution:ident, $fletcher:ty, $block_type:ty) => {
+            let data: Vec<$block_type> = (0..size_range.sample(&mut rng))
+                .map(|_| rng.gen())
+                .collect();
+            let (a0, b0): ($block_type, $block_type) = (rng.gen(), rng.gen());
+
+            let (a_delta, b_delta, len) = $contribution(&data);
+            assert_eq!(len, data.len());
+
+            let mut expected = <$fletcher>::with_initial_values(a0, b0);
+            expected.update_with_slice(&data);
+
+            let applied = <$fletcher>::with_initial_values(
+                a0.wrapping_add(a_delta),
+                b0.wrapping_add(b_delta)
+                    .wrapping_add(a0.wrapping_mul(len as $block_type)),
+            );
+            assert_eq!(applied, expected, "mismatch on: {:?}", data);
+        };
+    }
+
+    for _ in 0..20 {
+        check!(fletcher16_contribution, Fletcher16, u8);
+        check!(fletcher32_contribution, Fletcher32, u16);
+        check!(fletcher64_contribution, Fletcher64, u32);
+        check!(fletcher128_contribution, Fletcher128, u64);
+        check!(fletcher256_contribution, Fletcher256, u128);
+    }
+
+    assert_eq!(fletcher16_contribution(b""), (0, 0, 0));
+}
+
+#[test]
+fn hasher_block_writes_same_as_bytes() {
+    use core::hash::Hasher;
+
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty, $write_block:ident) => {
+            let blocks: Vec<$block_type> = (0..100).map(|_| rng.gen()).collect();
+
+            let mut by_block = <$fletcher>::new();
+            let mut by_bytes = <$fletcher>::new();
+            for &block in &blocks {
+                by_block.$write_block(block);
+                by_bytes.write(&block.to_ne_bytes());
+            }
+
+            let mut by_slice = <$fletcher>::new();
+            by_slice.update_with_slice(&blocks);
+
+            assert_eq!(by_block, by_bytes);
+            assert_eq!(by_block, by_slice);
+            assert_eq!(by_block.finish(), by_bytes.finish());
+        };
+    }
+
+    check!(Fletcher16, u8, write_u8);
+    check!(Fletcher32, u16, write_u16);
+    check!(Fletcher64, u32, write_u32);
+    check!(Fletcher128, u64, write_u64);
+}
+
+#[test]
+fn hasher_int_writes_same_as_bytes() {
+    use core::hash::Hasher;
+
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($fletcher:ty, $($write_int:ident: $int_type:ty),+) => {
+            $(
+                let ints: Vec<$int_type> = (0..100).map(|_| rng.gen()).collect();
+
+                let mut by_int = <$fletcher>::new();
+                let mut by_bytes = <$fletcher>::new();
+                for &int in &ints {
+                    by_int.$write_int(int);
+                    by_bytes.write(&int.to_ne_bytes());
+                }
+
+                assert_eq!(
+                    by_int,
+                    by_bytes,
+                    "{} on {}",
+                    stringify!($write_int),
+                    stringify!($fletcher)
+                );
+            )+
+        };
+    }
+
+    check!(Fletcher16, write_u8: u8, write_u16: u16, write_u32: u32, write_u64: u64, write_u128: u128);
+    check!(Fletcher32, write_u16: u16, write_u32: u32, write_u64: u64, write_u128: u128);
+    check!(Fletcher64, write_u32: u32, write_u64: u64, write_u128: u128);
+    check!(Fletcher128, write_u64: u64, write_u128: u128);
+}
+
+#[test]
+fn checked_detects_overflow() {
+    let mut rng = rand::thread_rng();
+    let data: Vec<u32> = (0..1000).map(|_| rng.gen()).collect();
+
+    let mut checked = CheckedFletcher::<u64>::new();
+    checked.update_with_slice(&data);
+
+    let mut fletcher = Fletcher64::new();
+    fletcher.update_with_slice(&data);
+
+    assert!(checked.overflowed());
+    assert_eq!(checked.fletcher(), fletcher);
+
+    // Over the blocks 1 to n, b = n * (n + 1) * (n + 2) / 6, which first exceeds 16 bits at 73.
+    let mut checked = CheckedFletcher::<u32>::new();
+    checked.update_with_iter(1..73);
+    assert!(!checked.overflowed());
+    checked.update_with_iter(core::iter::once(73));
+    assert!(checked.overflowed());
+}
+
+/// A user-defined checksum, packing its sums the other way around from the built-in ones.
+#[derive(Copy, Clone, Default, Debug, PartialEq)]
+struct Swapped32(u32);
+
+impl FletcherChecksum for Swapped32 {
+    type BlockType = u16;
+
+    const BLOCK_BYTES: usize = 2;
+    const RESULT_BITS: u32 = 32;
+
+    fn from_components(a: u16, b: u16) -> Self {
+        Swapped32(((a as u32) << 16) | b as u32)
+    }
+}
+
+#[test]
+fn user_defined_checksum() {
+    let mut rng = rand::thread_rng();
+    let data: Vec<u16> = (0..1000).map(|_| rng.gen()).collect();
+
+    let mut custom = fletcher_simd::Fletcher::<Swapped32>::new();
+    custom.update_with_slice(&data);
+
+    let mut builtin = Fletcher32::new();
+    builtin.update_with_slice(&data);
+
+    assert_eq!(custom.components(), builtin.components());
+    assert_eq!(custom.value(), Swapped32(builtin.value().rotate_left(16)));
+}
+
+/// Checksums `data` through the `Checksum` trait alone, resetting midway to check that the first
+/// update is discarded.
+fn generic_checksum<C: Checksum + Default>(data: &[C::Block]) -> u128 {
+    let mut checksum = C::default();
+    checksum.update_with_slice(&data[..data.len() / 2]);
+    checksum.reset();
+    checksum.update_with_slice(data);
+    checksum.value()
+}
+
+#[test]
+fn checksum_trait_same_as_inherent() {
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty) => {
+            let data: Vec<$block_type> = (0..100).map(|_| rng.gen()).collect();
+
+            let mut fletcher = <$fletcher>::new();
+            fletcher.update_with_slice(&data);
+
+            assert_eq!(
+                generic_checksum::<$fletcher>(&data),
+                fletcher.value() as u128
+            );
+            assert_eq!(
+                <$fletcher as Checksum>::BLOCK_BYTES,
+                <$fletcher>::BLOCK_BYTES
+            );
+        };
+    }
+
+    check!(Fletcher16, u8);
+    check!(Fletcher32, u16);
+    check!(Fletcher64, u32);
+    check!(Fletcher128, u64);
+}
+
+#[test]
+fn default_endianness_follows_feature() {
+    let expected = if cfg!(feature = "be_default") {
+        Endianness::Big
+    } else {
+        Endianness::Little
+    };
+    assert_eq!(Endianness::DEFAULT, expected);
+    assert_eq!(Endianness::default(), expected);
+
+    let data = [0x01, 0x02, 0x03, 0x04];
+    let mut explicit = Fletcher32::new();
+    explicit.update_with_bytes(&data, expected);
+
+    let mut any = FletcherAny::new(32).unwrap();
+    any.update_with_slice(&data);
+    assert_eq!(any.value(), explicit.value() as u128);
+    assert_eq!(Fletcher32::try_from(&data[..]), Ok(explicit));
+    assert_eq!(const_fletcher32(&data), explicit.value());
+
+    // The methods that name their byte order are unaffected.
+    let mut little = Fletcher32::new();
+    little.update_with_slice_le(&data);
+    assert_eq!(little.components(), (0x0201 + 0x0403, 0x0201 * 2 + 0x0403));
+    let mut big = Fletcher32::new();
+    big.update_with_slice_be(&data);
+    assert_eq!(big.components(), (0x0102 + 0x0304, 0x0102 * 2 + 0x0304));
+}
+
+#[test]
+fn any_same_as_concrete() {
+    let mut rng = rand::thread_rng();
+    let data: Vec<u8> = (0..1001).map(|_| rng.gen()).collect();
+
+    let mut fletcher16 = Fletcher16::new();
+    fletcher16.update_with_slice(&data);
+    let mut fletcher32 = Fletcher32::new();
+    fletcher32.update_with_bytes(&data, Endianness::DEFAULT);
+    let mut fletcher64 = Fletcher64::new();
+    fletcher64.update_with_bytes(&data, Endianness::DEFAULT);
+    let mut fletcher128 = Fletcher128::new();
+    fletcher128.update_with_bytes(&data, Endianness::DEFAULT);
+
+    for (bits, expected) in [
+        (16, fletcher16.value() as u128),
+        (32, fletcher32.value() as u128),
+        (64, fletcher64.value() as u128),
+        (128, fletcher128.value()),
+    ] {
+        let mut any = FletcherAny::new(bits).unwrap();
+        assert_eq!(any.bits(), bits as u32);
+
+        // Split the input on a block boundary of every width, since only a trailing partial block
+        // is padded.
+        any.update_with_slice(&data[..512]);
+        any.update_with_slice(&data[512..]);
+        assert_eq!(any.value(), expected, "mismatch for {} bits", bits);
+
+        assert_eq!(
+            FletcherAny::try_from(bits as usize).unwrap(),
+            FletcherAny::new(bits).unwrap()
+        );
+    }
+
+    assert!(FletcherAny::new(0).is_none());
+    assert!(FletcherAny::new(256).is_none());
+    assert_eq!(FletcherAny::try_from(1 << 20).unwrap_err().bits(), 1 << 20);
+}
+
+#[test]
+fn array_same_as_slice() {
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty, $($len:literal),+) => {$(
+            let data: [$block_type; $len] = core::array::from_fn(|_| rng.gen());
+            let seed = <$fletcher>::with_initial_values(rng.gen(), rng.gen());
+
+            let mut expected = seed;
+            expected.update_with_slice(&data);
+
+            let mut fletcher = seed;
+            fletcher.update_array(&data);
+            assert_eq!(fletcher, expected, "mismatch for {} blocks", $len);
+        )+};
+    }
+
+    check!(Fletcher16, u8, 0, 1, 20, 31, 32, 33, 64, 100, 257);
+    check!(Fletcher32, u16, 0, 1, 15, 16, 17, 20, 64, 100, 257);
+    check!(Fletcher64, u32, 0, 1, 15, 16, 17, 20, 64, 100, 257);
+    check!(Fletcher128, u64, 0, 1, 20, 47, 48, 49, 64, 100, 257);
+}
+
+#[test]
+fn slice_around_simd_threshold_same_as_scalar() {
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty) => {
+            let threshold = <$fletcher>::SIMD_THRESHOLD;
+            let data: Vec<$block_type> = (0..threshold + <$fletcher>::LANES + 1)
+                .map(|_| rng.gen())
+                .collect();
+            let seed = <$fletcher>::with_initial_values(rng.gen(), rng.gen());
+
+            for len in threshold.saturating_sub(<$fletcher>::LANES + 1)..=data.len() {
+                let mut expected = seed;
+                expected.update_with_iter_scalar(data[..len].iter().copied());
+
+                let mut fletcher = seed;
+                fletcher.update_with_slice(&data[..len]);
+                assert_eq!(fletcher, expected, "mismatch for {} blocks", len);
+
+                for threshold in [0, len, len + 1, usize::MAX] {
+                    let mut fletcher = seed;
+                    fletcher.update_with_slice_threshold(&data[..len], threshold);
+                    assert_eq!(
+                        fletcher, expected,
+                        "mismatch for {} blocks with threshold {}",
+                        len, threshold
+                    );
+                }
+            }
+        };
+    }
+
+    check!(Fletcher16, u8);
+    check!(Fletcher32, u16);
+    check!(Fletcher64, u32);
+    check!(Fletcher128, u64);
+}
+
+#[test]
+fn lanes_checked_same_as_default() {
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($result_type:ty, $fletcher:ty, $block_type:ty) => {
+            let data: Vec<$block_type> = (0..1001).map(|_| rng.gen()).collect();
+            let mut expected = <$fletcher>::new();
+            expected.update_with_slice(&data);
+
+            for lanes in [1, 2, 4, 8, 16, 32, 64] {
+                let mut fletcher = LanesFletcher::<$result_type>::checked(lanes).unwrap();
+                assert_eq!(fletcher.lanes(), lanes);
+
+                fletcher.update_with_slice(&data[..500]);
+                fletcher.update_with_slice(&data[500..]);
+                assert_eq!(
+                    fletcher.fletcher(),
+                    expected,
+                    "mismatch with {} lanes",
+                    lanes
+                );
+            }
+
+            for lanes in [0, 3, 12, 128] {
+                let error = LanesFletcher::<$result_type>::checked(lanes).unwrap_err();
+                assert_eq!(error.lanes(), lanes);
+            }
+        };
+    }
+
+    check!(u16, Fletcher16, u8);
+    check!(u32, Fletcher32, u16);
+    check!(u64, Fletcher64, u32);
+    check!(u128, Fletcher128, u64);
+}
+
+#[test]
+fn buffered_short_updates_same_as_combined() {
+    let mut rng = rand::thread_rng();
+    let size_range = Uniform::from(0..40);
+
+    macro_rules! check {
+        ($result_type:ty, $fletcher:ty, $block_type:ty) => {
+            let mut buffered = BufferedFletcher::<$result_type>::new();
+            let mut data: Vec<$block_type> = Vec::new();
+
+            for i in 0..100 {
+                let size: usize = size_range.sample(&mut rng);
+                let part: Vec<$block_type> = (0..size).map(|_| rng.gen()).collect();
+
+                if i % 2 == 0 {
+                    buffered.update_with_iter(part.iter().copied());
+                } else {
+                    buffered.update_with_slice(&part);
+                }
+                data.extend(part);
+
+                let mut combined = <$fletcher>::new();
+                combined.update_with_iter(data.iter().copied());
+                assert_eq!(
+                    buffered.fletcher(),
+                    combined,
+                    "mismatch on checksum from: {:?}",
+                    data
+                );
+            }
+        };
+    }
+
+    check!(u16, Fletcher16, u8);
+    check!(u32, Fletcher32, u16);
+    check!(u64, Fletcher64, u32);
+    check!(u128, Fletcher128, u64);
+}
+
+#[test]
+fn swar_same_as_scalar() {
+    let mut rng = rand::thread_rng();
+    let size_range = Uniform::from(0..1025);
+
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty) => {
+            let size: usize = size_range.sample(&mut rng);
+            let data: Vec<$block_type> = (0..size).map(|_| rng.gen()).collect();
+            let initial = (rng.gen(), rng.gen());
+
+            let mut swar = <$fletcher>::from(initial);
+            swar.update_with_slice_swar(&data);
+
+            let mut scalar = <$fletcher>::from(initial);
+            scalar.update_with_iter_scalar(data.iter().copied());
+
+            assert_eq!(swar, scalar, "mismatch on checksum from: {:?}", data);
+        };
+    }
+
+    const NUM_ITERS: usize = 1000;
+    for _ in 0..NUM_ITERS {
+        check!(Fletcher16, u8);
+    }
+}
+
+#[test]
+fn const_same_as_runtime() {
+    const EXPECTED: u16 = const_fletcher16(b"abcde");
+    assert_eq!(EXPECTED, 0xC3EF);
+
+    let mut rng = rand::thread_rng();
+    for size in 0..=64 {
+        let data: Vec<u8> = (0..size).map(|_| rng.gen()).collect();
+
+        macro_rules! check {
+            ($const_fn:ident, $fletcher:ty) => {
+                let mut fletcher = <$fletcher>::new();
+                fletcher.update_with_bytes(&data, Endianness::DEFAULT);
+                assert_eq!(
+                    $const_fn(&data),
+                    fletcher.value(),
+                    "mismatch on {} from: {:?}",
+                    stringify!($const_fn),
+                    data
+                );
+            };
+        }
+
+        check!(const_fletcher16, Fletcher16);
+        check!(const_fletcher32, Fletcher32);
+        check!(const_fletcher64, Fletcher64);
+        check!(const_fletcher128, Fletcher128);
+        check!(const_fletcher256, Fletcher256);
+    }
+}
+
+#[test]
+fn from_bytes_strict() {
+    let data: Vec<u8> = (0..64).collect();
+
+    let mut expected = Fletcher16::new();
+    expected.update_with_slice(&data);
+    assert_eq!(Fletcher16::from(&data[..]), expected);
+
+    let mut expected = Fletcher64::new();
+    expected.update_with_bytes(&data, Endianness::DEFAULT);
+    assert_eq!(Fletcher64::try_from(&data[..]), Ok(expected));
+
+    let err = Fletcher64::try_from(&data[..63]).unwrap_err();
+    assert_eq!((err.input_len(), err.block_bytes()), (63, 4));
+    assert_eq!(
+        err.to_string(),
+        "length 63 is not a multiple of the block size 4"
+    );
+    assert!(Fletcher256::try_from(&data[..40]).is_err());
+    assert!(Fletcher32::try_from(&data[..0]).is_ok());
+}
+
+#[test]
+fn ones_seed() {
+    assert_eq!(
+        Fletcher32::with_ones_seed(),
+        Fletcher32::with_initial_values(1, 0)
+    );
+    assert_eq!(
+        Fletcher256::with_ones_seed(),
+        Fletcher256::with_initial_values(1, 0)
+    );
+
+    // Unlike the default seed, leading zero blocks change the checksum.
+    let mut fletcher = Fletcher32::with_ones_seed();
+    fletcher.update_with_slice(&[0, 0]);
+    assert_eq!(fletcher.value(), 0x0002_0001);
+}
+
+#[test]
+fn combine_same_as_concatenated() {
+    let mut rng = rand::thread_rng();
+    let size_range = Uniform::from(0..129);
+
+    const NUM_ITERS: usize = 1000;
+    for _ in 0..NUM_ITERS {
+        let size: usize = size_range.sample(&mut rng);
+        let data: Vec<u16> = (0..size).map(|_| rng.gen()).collect();
+        let (first, second) = data.split_at(rng.gen_range(0..=size));
+
+        let mut whole = Fletcher32::new();
+        whole.update_with_slice(&data);
+
+        let mut combined = Fletcher32::new();
+        combined.update_with_slice(first);
+        let mut other = Fletcher32::new();
+        other.update_with_slice(second);
+        combined.combine(&other, second.len());
+
+        assert_eq!(whole, combined, "mismatch on checksum from: {:?}", data);
+
+        let mut summed = Fletcher32::new();
+        summed.update_with_slice(first);
+        summed += (other, second.len());
+
+        assert_eq!(combined, summed, "mismatch on += from: {:?}", data);
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_same_as_serial() {
+    let mut rng = rand::thread_rng();
+    let size_range = Uniform::from(0..(1 << 20));
+
+    const NUM_ITERS: usize = 20;
+    for _ in 0..NUM_ITERS {
+        let size: usize = size_range.sample(&mut rng);
+        let data: Vec<u8> = (0..size).map(|_| rng.gen()).collect();
+        let seed = (rng.gen(), rng.gen());
+
+        let mut serial = Fletcher16::with_initial_values(seed.0, seed.1);
+        let mut parallel = Fletcher16::with_initial_values(seed.0, seed.1);
+        serial.update_with_slice(&data);
+        parallel.par_update_with_slice(&data);
+
+        assert_eq!(serial, parallel, "mismatch on checksum of {} bytes", size);
+    }
+}
+
+#[test]
+fn checksum_each_same_as_fresh_objects() {
+    let data: Vec<u16> = (0..1000).collect();
+    let records: Vec<&[u16]> = data.chunks(97).chain([&[][..], &data[..]]).collect();
+
+    let expected: Vec<u32> = records
+        .iter()
+        .map(|record| {
+            let mut fletcher = Fletcher32::new();
+            fletcher.update_with_slice(record);
+            fletcher.value()
+        })
+        .collect();
+
+    assert_eq!(Fletcher32::checksum_each(records.iter().copied()), expected);
+    #[cfg(feature = "rayon")]
+    assert_eq!(Fletcher32::par_checksum_each(records.clone()), expected);
+
+    let mut reused = Fletcher32::new();
+    reused.update_with_slice(&data);
+    reused.reset();
+    assert_eq!(reused, Fletcher32::new());
+}
+
+#[test]
+fn batch_same_as_per_buffer() {
+    let mut rng = rand::thread_rng();
+
+    // Fewer, exactly as many, and more buffers than lanes, including a partial last group, and
+    // lengths that are empty, shorter than a tile of blocks, and several tiles and a tail.
+    for (num_buffers, len) in [0, 1, 4, 31, 32, 33, 70]
+        .iter()
+        .flat_map(|&num_buffers| [0, 5, 200].map(|len| (num_buffers, len)))
+    {
+        let data: Vec<Vec<u8>> = (0..num_buffers)
+            .map(|_| (0..len).map(|_| rng.gen()).collect())
+            .collect();
+        let buffers: Vec<&[u8]> = data.iter().map(Vec::as_slice).collect();
+        assert_eq!(
+            batch_fletcher16(&buffers),
+            Fletcher16::checksum_each(buffers.iter().copied())
+        );
+
+        let data: Vec<Vec<u16>> = data
+            .iter()
+            .map(|d| d.iter().map(|&x| u16::from(x) * 257).collect())
+            .collect();
+        let buffers: Vec<&[u16]> = data.iter().map(Vec::as_slice).collect();
+        assert_eq!(
+            batch_fletcher32(&buffers),
+            Fletcher32::checksum_each(buffers.iter().copied())
+        );
+
+        let data: Vec<Vec<u32>> = data
+            .iter()
+            .map(|d| d.iter().map(|&x| u32::from(x) * 65537).collect())
+            .collect();
+        let buffers: Vec<&[u32]> = data.iter().map(Vec::as_slice).collect();
+        assert_eq!(
+            batch_fletcher64(&buffers),
+            Fletcher64::checksum_each(buffers.iter().copied())
+        );
+
+        let data: Vec<Vec<u64>> = data
+            .iter()
+            .map(|d| d.iter().map(|&x| u64::from(x) << 31 | 1).collect())
+            .collect();
+        let buffers: Vec<&[u64]> = data.iter().map(Vec::as_slice).collect();
+        assert_eq!(
+            batch_fletcher128(&buffers),
+            Fletcher128::checksum_each(buffers.iter().copied())
+        );
+    }
+
+    // Buffers of different lengths fall back to checksumming each on its own.
+    let buffers: [&[u8]; 3] = [b"abcde", b"", b"abcdefgh"];
+    assert_eq!(batch_fletcher16(&buffers), vec![0xC3EF, 0, 0xF824]);
+}
+
+// Regression test for the SIMD kernel leaving out `a * num_blocks` from `b`, which only shows
+// when a vector run starts from a non-zero `a`: a seeded checksum, or any update after the first.
+#[test]
+fn simd_scalar_same_seeded() {
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty) => {{
+            // Long enough for every width to take the SIMD path on each of the two updates.
+            let size_range =
+                Uniform::from(<$fletcher>::SIMD_THRESHOLD..4 * <$fletcher>::SIMD_THRESHOLD);
+
+            const NUM_ITERS: usize = 250;
+            for _ in 0..NUM_ITERS {
+                let seed = <$fletcher>::with_initial_values(rng.gen(), rng.gen());
+                let first: Vec<$block_type> = (0..size_range.sample(&mut rng))
+                    .map(|_| rng.gen())
+                    .collect();
+                let second: Vec<$block_type> = (0..size_range.sample(&mut rng))
+                    .map(|_| rng.gen())
+                    .collect();
+
+                let mut simd = seed;
+                simd.update_with_slice(&first);
+                simd.update_with_slice(&second);
+
+                let mut scalar = seed;
+                scalar.update_with_iter_scalar(first.iter().chain(&second).copied());
+
+                assert_eq!(
+                    simd, scalar,
+                    "mismatch on checksum from {:?} then {:?} seeded with {:?}",
+                    first, second, seed
+                );
+            }
+        }};
+    }
+
+    check!(Fletcher16, u8);
+    check!(Fletcher32, u16);
+    check!(Fletcher64, u32);
+    check!(Fletcher128, u64);
+}
+
+// Runs under both static and runtime dispatch, covering every remainder length for each width.
+#[test]
+fn dispatch_same_as_scalar_every_length() {
+    let data: Vec<u8> = (0..=255).collect();
+
+    for len in 0..=data.len() {
+        let bytes = &data[..len];
+
+        let mut simd = Fletcher16::new();
+        let mut scalar = Fletcher16::new();
+        simd.update_with_slice(bytes);
+        scalar.update_with_iter_scalar(bytes.iter().copied());
+        assert_eq!(simd, scalar, "Fletcher16 mismatch at length {}", len);
+
+        let mut simd = Fletcher32::new();
+        let mut scalar = Fletcher32::new();
+        simd.update_with_slice_le(bytes);
+        scalar.update_with_iter_scalar(
+            bytes
+                .chunks(2)
+                .map(|c| c.iter().rev().fold(0, |acc, &x| acc << 8 | u16::from(x))),
+        );
+        assert_eq!(simd, scalar, "Fletcher32 mismatch at length {}", len);
+
+        let mut simd = Fletcher64::new();
+        let mut scalar = Fletcher64::new();
+        simd.update_with_slice_le(bytes);
+        scalar.update_with_iter_scalar(
+            bytes
+                .chunks(4)
+                .map(|c| c.iter().rev().fold(0, |acc, &x| acc << 8 | u32::from(x))),
+        );
+        assert_eq!(simd, scalar, "Fletcher64 mismatch at length {}", len);
+
+        let mut simd = Fletcher128::new();
+        let mut scalar = Fletcher128::new();
+        simd.update_with_slice_le(bytes);
+        scalar.update_with_iter_scalar(
+            bytes
+                .chunks(8)
+                .map(|c| c.iter().rev().fold(0, |acc, &x| acc << 8 | u64::from(x))),
+        );
+        assert_eq!(simd, scalar, "Fletcher128 mismatch at length {}", len);
+    }
+}
+
+#[test]
+fn components_round_trip() {
+    let mut fletcher = Fletcher32::new();
+    fletcher.update_with_slice(&[1, 2, 3]);
+    assert_eq!(fletcher.components(), (6, 10));
+
+    let mut restored = Fletcher32::from(fletcher.components());
+    assert_eq!(restored, fletcher);
+
+    fletcher.update_with_slice(&[4, 5]);
+    restored.update_with_slice(&[4, 5]);
+    assert_eq!(restored.value(), fletcher.value());
+
+    let from_tuple: Fletcher16 = (0x24, 0xF8).into();
+    assert_eq!(from_tuple.value(), 0xF824);
+}
+
+#[test]
+fn counting_tracks_every_update_path() {
+    let mut rng = rand::thread_rng();
+    let size_range = Uniform::from(0..100);
+
+    let mut counting = CountingFletcher::<u32>::new();
+    let mut data: Vec<u16> = Vec::new();
+    assert!(counting.is_empty());
+
+    for i in 0..30 {
+        let size: usize = size_range.sample(&mut rng);
+        let part: Vec<u16> = (0..size).map(|_| rng.gen()).collect();
+
+        match i % 3 {
+            0 => counting.update_with_slice(&part),
+            1 => counting.update_with_iter(part.iter().copied()),
+            _ => counting.update_with_iter_scalar(part.iter().copied()),
+        }
+        data.extend(part);
+
+        let mut fletcher = Fletcher32::new();
+        fletcher.update_with_slice(&data);
+
+        assert_eq!(counting.len(), data.len() as u64);
+        assert_eq!(counting.fletcher(), fletcher);
+        assert_eq!(
+            counting.value_with_length(),
+            counting.fletcher().value_with_length(data.len())
+        );
+    }
+
+    let mut other = CountingFletcher::<u32>::new();
+    other.update_with_slice(&[1, 2, 3]);
+    counting.combine(&other);
+    data.extend([1, 2, 3]);
+
+    let mut fletcher = Fletcher32::new();
+    fletcher.update_with_slice(&data);
+
+    assert_eq!(counting.len(), data.len() as u64);
+    assert_eq!(counting.fletcher(), fletcher);
+}
+
+#[test]
+fn counting_tracks_forwarded_update_paths() {
+    let mut rng = rand::thread_rng();
+    let bytes: Vec<u8> = (0..1000).map(|_| rng.gen()).collect();
+
+    // Byte inputs count the bytes rounded up to whole blocks of 4.
+    for &len in &[0, 1, 4, 7, 999, 1000] {
+        let part = &bytes[..len];
+        let blocks = ((len + 3) / 4) as u64;
+        let mut expected = Fletcher64::new();
+        expected.update_with_bytes(part, Endianness::Little);
+
+        let mut counting = CountingFletcher::<u64>::new();
+        counting.update_with_bytes(part, Endianness::Little);
+        assert_eq!((counting.len(), counting.fletcher()), (blocks, expected));
+
+        let mut counting = CountingFletcher::<u64>::new();
+        counting.update_with_slice_le(part);
+        assert_eq!((counting.len(), counting.fletcher()), (blocks, expected));
+
+        let mut counting = CountingFletcher::<u64>::new();
+        counting
+            .update_with_reader(part, Endianness::Little)
+            .unwrap();
+        assert_eq!((counting.len(), counting.fletcher()), (blocks, expected));
+
+        let mut counting = CountingFletcher::<u64>::new();
+        let mut reader = std::io::BufReader::with_capacity(3, part);
+        assert_eq!(
+            counting
+                .update_with_bufread(&mut reader, Endianness::Little)
+                .unwrap(),
+            len as u64
+        );
+        assert_eq!((counting.len(), counting.fletcher()), (blocks, expected));
+
+        let mut expected = Fletcher64::new();
+        expected.update_with_bytes(part, Endianness::Big);
+        let mut counting = CountingFletcher::<u64>::new();
+        counting.update_with_slice_be(part);
+        assert_eq!((counting.len(), counting.fletcher()), (blocks, expected));
+    }
+
+    // Only the whole blocks before a read error are checksummed and counted.
+    struct FailAfter<'a>(&'a [u8]);
+
+    impl std::io::Read for FailAfter<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.0.is_empty() {
+                return Err(std::io::ErrorKind::Other.into());
+            }
+            let read = self.0.len().min(buf.len());
+            buf[..read].copy_from_slice(&self.0[..read]);
+            self.0 = &self.0[read..];
+            Ok(read)
+        }
+    }
+
+    let mut counting = CountingFletcher::<u64>::new();
+    assert!(counting
+        .update_with_reader(FailAfter(&bytes[..7]), Endianness::Little)
+        .is_err());
+    let mut expected = Fletcher64::new();
+    expected.update_with_bytes(&bytes[..4], Endianness::Little);
+    assert_eq!((counting.len(), counting.fletcher()), (1, expected));
+
+    let mut counting = CountingFletcher::<u64>::new();
+    let mut reader = std::io::BufReader::with_capacity(3, FailAfter(&bytes[..7]));
+    assert!(counting
+        .update_with_bufread(&mut reader, Endianness::Little)
+        .is_err());
+    assert_eq!((counting.len(), counting.fletcher()), (1, expected));
+
+    let mut counting = CountingFletcher::<u16>::new();
+    counting.update_repeated(0xFF, 300);
+    assert_eq!(counting.len(), 300);
+    assert_eq!(counting.fletcher(), Fletcher16::from(&[0xFF; 300][..]));
+
+    let data: Vec<u32> = (0..100).map(|_| rng.gen()).collect();
+    let mut expected = Fletcher64::new();
+    expected.update_with_slice(&data);
+
+    let mut counting = CountingFletcher::<u64>::new();
+    counting.update_with_slice_lanes::<4>(&data);
+    assert_eq!(counting.len(), 100);
+    assert_eq!(counting.fletcher(), expected);
+
+    let signed: Vec<i32> = data.iter().map(|&elem| elem as i32).collect();
+    let mut counting = CountingFletcher::<u64>::new();
+    counting.update_with_slice_signed(&signed);
+    assert_eq!(counting.len(), 100);
+    assert_eq!(counting.fletcher(), expected);
+
+    let mut counting = CountingFletcher::<U256>::new();
+    counting.update_with_slice_le(&bytes[..17]);
+    assert_eq!(counting.len(), 2);
+}
+
+#[test]
+fn counting_add_same_as_concatenated() {
+    let mut rng = rand::thread_rng();
+    let data: Vec<u32> = (0..300).map(|_| rng.gen()).collect();
+
+    let parts: Vec<CountingFletcher<u64>> = data
+        .chunks(37)
+        .map(|chunk| {
+            let mut part = CountingFletcher::<u64>::new();
+            part.update_with_slice(chunk);
+            part
+        })
+        .collect();
+
+    let mut whole = CountingFletcher::<u64>::new();
+    whole.update_with_slice(&data);
+
+    let summed = parts
+        .iter()
+        .fold(CountingFletcher::<u64>::new(), |acc, &part| acc + part);
+    assert_eq!(summed, whole);
+
+    let mut accumulated = CountingFletcher::<u64>::new();
+    for part in parts {
+        accumulated += part;
+    }
+    assert_eq!(accumulated, whole);
+}
+
+#[test]
+fn repeated_same_as_loop() {
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty) => {
+            // Counts past 256 and 65536 make the run cross the wrapping boundary of the narrower
+            // block types.
+            for &count in &[
+                0, 1, 2, 3, 255, 256, 257, 1000, 65535, 65536, 65537, 100_001,
+            ] {
+                let elem: $block_type = rng.gen();
+                let initial = (rng.gen(), rng.gen());
+
+                let mut repeated = <$fletcher>::from(initial);
+                repeated.update_repeated(elem, count);
+
+                let mut looped = <$fletcher>::from(initial);
+                looped.update_with_iter_scalar(core::iter::repeat(elem).take(count));
+
+                assert_eq!(repeated, looped, "mismatch on {} copies of {}", count, elem);
+            }
+        };
+    }
+
+    check!(Fletcher16, u8);
+    check!(Fletcher32, u16);
+    check!(Fletcher64, u32);
+    check!(Fletcher128, u64);
+    check!(Fletcher256, u128);
+}
+
+#[cfg(feature = "capi")]
+#[test]
+fn capi_same_as_rust() {
+    use fletcher_simd::capi::{
+        fletcher_simd_fletcher32_new, fletcher_simd_fletcher32_update,
+        fletcher_simd_fletcher32_value,
+    };
+
+    let mut rng = rand::thread_rng();
+    let data: Vec<u16> = (0..1000).map(|_| rng.gen()).collect();
+
+    let mut state = fletcher_simd_fletcher32_new();
+    unsafe {
+        fletcher_simd_fletcher32_update(&mut state, data.as_ptr(), 500);
+        fletcher_simd_fletcher32_update(&mut state, data[500..].as_ptr(), 500);
+        fletcher_simd_fletcher32_update(&mut state, core::ptr::null(), 0);
+    }
+
+    let mut fletcher = Fletcher32::new();
+    fletcher.update_with_slice(&data);
+
+    assert_eq!(state, fletcher);
+    assert_eq!(
+        unsafe { fletcher_simd_fletcher32_value(&state) },
+        fletcher.value()
+    );
+}
+
+#[test]
+fn reflected_same_as_reversed_reference() {
+    let mut rng = rand::thread_rng();
+    let size_range = Uniform::from(0..300);
+
+    /// Reverses the bits of each block one bit at a time, independently of `reverse_bits`.
+    macro_rules! reflect {
+        ($elem:expr, $block_type:ty) => {{
+            let elem: $block_type = $elem;
+            (0..<$block_type>::BITS).fold(0 as $block_type, |reflected, bit| {
+                reflected | (((elem >> bit) & 1) << (<$block_type>::BITS - 1 - bit))
+            })
+        }};
+    }
+
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty) => {
+            let size: usize = size_range.sample(&mut rng);
+            let data: Vec<$block_type> = (0..size).map(|_| rng.gen()).collect();
+
+            let mut reflected = <$fletcher>::new();
+            reflected.update_with_slice_reflected(&data);
+
+            let mut reference = <$fletcher>::new();
+            reference.update_with_iter_scalar(data.iter().map(|&elem| reflect!(elem, $block_type)));
+
+            assert_eq!(
+                reflected, reference,
+                "mismatch on checksum from: {:?}",
+                data
+            );
+        };
+    }
+
+    const NUM_ITERS: usize = 100;
+    for _ in 0..NUM_ITERS {
+        check!(Fletcher16, u8);
+        check!(Fletcher32, u16);
+        check!(Fletcher64, u32);
+        check!(Fletcher128, u64);
+    }
+}
+
+#[test]
+fn value_with_length_separates_zero_runs() {
+    let values: Vec<u64> = (0..64)
+        .map(|len| {
+            let mut fletcher = Fletcher64::new();
+            fletcher.update_with_slice(&vec![0; len]);
+            assert_eq!(fletcher.value(), 0);
+            fletcher.value_with_length(len)
+        })
+        .collect();
+
+    for (i, value) in values.iter().enumerate() {
+        assert!(!values[..i].contains(value), "collision at length {}", i);
+    }
+
+    // Lengths that only differ above the low block of the length, which a length truncated to
+    // the block size would not tell apart.
+    macro_rules! check_wide_gap {
+        ($counting:ty, $block_type:ty, $gap:expr) => {{
+            let mut short = <$counting>::new();
+            short.update_with_slice(&[0]);
+            let mut long = <$counting>::new();
+            long.update_with_slice(&vec![0 as $block_type; 1 + $gap]);
+
+            assert_eq!(short.value(), long.value());
+            assert_ne!(
+                short.fletcher().value_with_length(1),
+                long.fletcher().value_with_length(1 + $gap)
+            );
+            assert_ne!(short.value_with_length(), long.value_with_length());
+        }};
+    }
+
+    check_wide_gap!(CountingFletcher<u16>, u8, 1 << 8);
+    check_wide_gap!(CountingFletcher<u32>, u16, 1 << 16);
+
+    // The plain value is unchanged by the mixing.
+    let mut fletcher = Fletcher32::new();
+    fletcher.update_with_slice(&[1, 2, 3]);
+    let _ = fletcher.value_with_length(3);
+    assert_eq!(fletcher.value(), 0x000A_0006);
+}
+
+#[test]
+fn from_value_round_trip() {
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty) => {
+            let data: Vec<$block_type> = (0..100).map(|_| rng.gen()).collect();
+
+            let mut fletcher = <$fletcher>::new();
+            fletcher.update_with_slice(&data);
+
+            assert_eq!(<$fletcher>::from_value(fletcher.value()), fletcher);
+        };
+    }
+
+    check!(Fletcher16, u8);
+    check!(Fletcher32, u16);
+    check!(Fletcher64, u32);
+    check!(Fletcher128, u64);
+    check!(Fletcher256, u128);
+}
+
+#[test]
+fn reset_with_value_same_as_from_value() {
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty) => {
+            let stale: Vec<$block_type> = (0..37).map(|_| rng.gen()).collect();
+            let data: Vec<$block_type> = (0..100).map(|_| rng.gen()).collect();
+            let mut stored = <$fletcher>::new();
+            stored.update_with_slice(&stale[..5]);
+            let stored = stored.value();
+
+            let mut reused = <$fletcher>::new();
+            reused.update_with_slice(&stale);
+            reused.reset_with_value(stored);
+            reused.update_with_slice(&data);
+
+            let mut fresh = <$fletcher>::from_value(stored);
+            fresh.update_with_slice(&data);
+
+            assert_eq!(reused, fresh, "{}", stringify!($fletcher));
+        };
+    }
+
+    check!(Fletcher16, u8);
+    check!(Fletcher32, u16);
+    check!(Fletcher64, u32);
+    check!(Fletcher128, u64);
+    check!(Fletcher256, u128);
+
+    // The held-back bytes and blocks of the wrappers are discarded, not carried into the
+    // resumed checksum.
+    let mut stored = Fletcher32::new();
+    stored.update_with_bytes(b"abcd", Endianness::Little);
+    let stored = stored.value();
+
+    let mut streaming = StreamingFletcher::<u32>::new(Endianness::Little);
+    streaming.update_bytes(b"xyz");
+    streaming.reset_with_value(stored);
+    streaming.update_bytes(b"efgh");
+
+    let mut buffered = BufferedFletcher::<u32>::new();
+    buffered.update_with_slice(&[1, 2, 3]);
+    buffered.reset_with_value(stored);
+    buffered.update_with_slice(&[0x6665, 0x6867]);
+
+    let mut fresh = Fletcher32::from_value(stored);
+    fresh.update_with_bytes(b"efgh", Endianness::Little);
+
+    assert_eq!(streaming.finalize(), fresh.value());
+    assert_eq!(buffered.value(), fresh.value());
+}
+
+// Threads cannot be spawned on WASI.
+#[cfg(not(target_os = "wasi"))]
+#[test]
+fn shared_concurrent_appends() {
+    const THREADS: usize = 8;
+    const APPENDS: usize = 200;
+
+    // Equal slices give the same checksum in any order, so the result is deterministic.
+    let chunk: Vec<u16> = (0..37).collect();
+    let shared = SharedFletcher::<u32>::new();
+    std::thread::scope(|scope| {
+        for _ in 0..THREADS {
+            scope.spawn(|| (0..APPENDS).for_each(|_| shared.update_with_slice(&chunk)));
+        }
+    });
+
+    let mut expected = Fletcher32::new();
+    (0..THREADS * APPENDS).for_each(|_| expected.update_with_slice(&chunk));
+    assert_eq!(shared.value(), expected.value());
+
+    // Distinct slices interleave in an unknown order. Each one sums to the same `a`, so whole
+    // slices give the same checksum in any order, but blocks of different slices interleaved
+    // with each other would be weighted differently.
+    let chunk = |thread: usize, append: usize| {
+        let (x, y) = (thread as u16, append as u16);
+        [x, y, 0u16.wrapping_sub(x).wrapping_sub(y)]
+    };
+    let shared = SharedFletcher::<u32>::new();
+    std::thread::scope(|scope| {
+        for thread in 0..THREADS {
+            let shared = &shared;
+            scope.spawn(move || {
+                (0..APPENDS).for_each(|append| shared.update_with_slice(&chunk(thread, append)))
+            });
+        }
+    });
+
+    let mut expected = Fletcher32::new();
+    for thread in 0..THREADS {
+        (0..APPENDS).for_each(|append| expected.update_with_slice(&chunk(thread, append)));
+    }
+    assert_eq!(shared.into_inner(), expected);
+}
+
+#[test]
+fn framed_round_trip() {
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($fletcher:ty) => {
+            for endianness in [Endianness::Little, Endianness::Big] {
+                // A length that is not a multiple of any block size, so the payload is padded.
+                let mut frame: Vec<u8> = (0..101).map(|_| rng.gen()).collect();
+                let mut fletcher = <$fletcher>::new();
+                fletcher.update_with_bytes(&frame, endianness);
+                fletcher.append_to(&mut frame, endianness);
+
+                assert_eq!(frame.len(), 101 + core::mem::size_of_val(&fletcher.value()));
+                assert!(<$fletcher>::verify_framed(&frame, endianness));
+
+                frame[0] ^= 1;
+                assert!(!<$fletcher>::verify_framed(&frame, endianness));
+            }
+
+            // Every frame shorter than a trailer is rejected rather than read out of bounds,
+            // including one that would be all zeroes, the trailer of an empty payload.
+            let trailer_len = core::mem::size_of_val(&<$fletcher>::new().value());
+            for len in 0..trailer_len {
+                assert!(!<$fletcher>::verify_framed(
+                    &vec![0; len],
+                    Endianness::Little
+                ));
+            }
+
+            // An empty payload is valid, and so is its trailer alone.
+            let mut frame = Vec::new();
+            <$fletcher>::new().append_to(&mut frame, Endianness::Big);
+            assert_eq!(frame.len(), trailer_len);
+            assert!(<$fletcher>::verify_framed(&frame, Endianness::Big));
+
+            // A corrupted trailer is caught as well as a corrupted payload.
+            let mut frame: Vec<u8> = (0..64).map(|_| rng.gen()).collect();
+            let mut fletcher = <$fletcher>::new();
+            fletcher.update_with_bytes(&frame, Endianness::Little);
+            fletcher.append_to(&mut frame, Endianness::Little);
+            *frame.last_mut().unwrap() ^= 0x80;
+            assert!(!<$fletcher>::verify_framed(&frame, Endianness::Little));
+        };
+    }
+
+    check!(Fletcher16);
+    check!(Fletcher32);
+    check!(Fletcher64);
+    check!(Fletcher128);
+    check!(Fletcher256);
+}
+
+#[test]
+fn into_byte_array_is_big_endian_value() {
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty, $len:literal) => {
+            let data: Vec<$block_type> = (0..100).map(|_| rng.gen()).collect();
+
+            let mut fletcher = <$fletcher>::new();
+            fletcher.update_with_slice(&data);
+
+            let bytes: [u8; $len] = fletcher.into();
+            assert_eq!(bytes, fletcher.value().to_be_bytes());
+
+            let mut appended = Vec::new();
+            fletcher.append_to(&mut appended, Endianness::Big);
+            assert_eq!(appended, bytes);
+        };
+    }
+
+    check!(Fletcher16, u8, 2);
+    check!(Fletcher32, u16, 4);
+    check!(Fletcher64, u32, 8);
+    check!(Fletcher128, u64, 16);
+    check!(Fletcher256, u128, 32);
+}
+
+#[test]
+fn streaming_same_as_whole_at_odd_splits() {
+    // A fixed array rather than a `Vec`, since the streaming object itself never allocates.
+    let mut data = [0u8; 301];
+    rand::thread_rng().fill(&mut data[..]);
+
+    macro_rules! check {
+        ($result_type:ty, $fletcher:ty) => {
+            for endianness in [Endianness::Little, Endianness::Big] {
+                let mut whole = <$fletcher>::new();
+                whole.update_with_bytes(&data, endianness);
+
+                // Chunks of 0 to 16 bytes, so that partial blocks of every width carry over.
+                for step in 1..17 {
+                    let mut streaming = StreamingFletcher::<$result_type>::new(endianness);
+                    let mut rest = &data[..];
+                    let mut len = 0;
+                    while !rest.is_empty() {
+                        len = (len + step) % 17;
+                        let (chunk, tail) = rest.split_at(len.min(rest.len()));
+                        streaming.update_bytes(chunk);
+                        rest = tail;
+                    }
+
+                    assert_eq!(streaming.finalize(), whole.value(), "step {}", step);
+                }
+            }
+        };
+    }
+
+    check!(u16, Fletcher16);
+    check!(u32, Fletcher32);
+    check!(u64, Fletcher64);
+    check!(u128, Fletcher128);
+    check!(U256, Fletcher256);
+}
+
+#[test]
+fn format_radix_same_as_std_formatting() {
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($fletcher:ty, $result_type:ty, $binary:literal, $decimal:literal, $hex:literal) => {
+            for value in [0, 1, <$result_type>::MAX, rng.gen()] {
+                let fletcher = <$fletcher>::from_value(value);
+                assert_eq!(
+                    fletcher.format_radix(2),
+                    format!("{:0w$b}", value, w = $binary)
+                );
+                assert_eq!(
+                    fletcher.format_radix(10),
+                    format!("{:0w$}", value, w = $decimal)
+                );
+                assert_eq!(
+                    fletcher.format_radix(16),
+                    format!("{:0w$x}", value, w = $hex)
+                );
+            }
+        };
+    }
+
+    check!(Fletcher16, u16, 16, 5, 4);
+    check!(Fletcher32, u32, 32, 10, 8);
+    check!(Fletcher64, u64, 64, 20, 16);
+    check!(Fletcher128, u128, 128, 39, 32);
+
+    let max = Fletcher256::with_initial_values(u128::MAX, u128::MAX);
+    assert_eq!(max.format_radix(2), "1".repeat(256));
+    assert_eq!(
+        max.format_radix(10),
+        "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+    );
+    assert_eq!(max.format_radix(16), "f".repeat(64));
+    assert_eq!(
+        Fletcher256::with_initial_values(0xAB, 1).format_radix(16),
+        format!("{:031x}1{:032x}", 0, 0xAB)
+    );
+
+    assert_eq!(Fletcher32::from_value(35).format_radix(36), "000000z");
+    assert_eq!(Fletcher32::from_value(u32::MAX).format_radix(36), "1z141z3");
+}
+
+#[test]
+#[should_panic(expected = "radix must be in the range 2 to 36, got 37")]
+fn format_radix_rejects_large_radix() {
+    let _ = Fletcher16::new().format_radix(37);
+}
+
+#[test]
+fn hash_value_spreads_sequential_inputs() {
+    const NUM_BUCKETS: usize = 1024;
+
+    // Bucket sequential keys by the low bits of each hash, as a hash table would.
+    let mut raw_buckets = vec![0; NUM_BUCKETS];
+    let mut mixed_buckets = vec![0; NUM_BUCKETS];
+    for i in 0..4 * NUM_BUCKETS {
+        let mut fletcher = Fletcher64::new();
+        fletcher.update_with_bytes(format!("key{}", i).as_bytes(), Endianness::Little);
+        raw_buckets[fletcher.value() as usize % NUM_BUCKETS] += 1;
+        mixed_buckets[fletcher.hash_value() as usize % NUM_BUCKETS] += 1;
+    }
+
+    // The low bits of the raw value are the low bits of `a`, the sum of the words of the key,
+    // which only differ in their last few bytes. With four keys per bucket, a uniform hash is
+    // expected to leave about 2% of the buckets empty.
+    let used = |buckets: &[usize]| buckets.iter().filter(|&&count| count > 0).count();
+    assert!(used(&raw_buckets) < NUM_BUCKETS / 10);
+    assert!(used(&mixed_buckets) > NUM_BUCKETS * 9 / 10);
+    assert!(mixed_buckets.iter().max().unwrap() < raw_buckets.iter().max().unwrap());
+}
+
+#[test]
+fn builder_combinations_same_as_direct() {
+    let mut rng = rand::thread_rng();
+    let bytes: Vec<u8> = (0..101).map(|_| rng.gen()).collect();
+    let blocks: Vec<u32> = (0..100).map(|_| rng.gen()).collect();
+
+    // The default options are the same as a new checksum.
+    let mut configured = FletcherBuilder::new().build::<u32>();
+    configured.update_with_bytes(&bytes);
+    let mut fletcher = Fletcher32::new();
+    fletcher.update_with_bytes(&bytes, Endianness::DEFAULT);
+    assert_eq!(configured.value(), fletcher.value());
+
+    // Seeded and big-endian.
+    let mut configured = FletcherBuilder::new()
+        .seed(7, 11)
+        .endianness(Endianness::Big)
+        .build::<u32>();
+    configured.update_with_bytes(&bytes);
+    let mut fletcher = Fletcher32::with_initial_values(7, 11);
+    fletcher.update_with_bytes(&bytes, Endianness::Big);
+    assert_eq!(configured.value(), fletcher.value());
+
+    // Reflected, from both blocks and bytes.
+    let mut configured = FletcherBuilder::new().reflected().build::<u64>();
+    configured.update_with_slice(&blocks);
+    configured.update_with_bytes(&bytes);
+    let mut fletcher = Fletcher64::new();
+    fletcher.update_with_slice_reflected(&blocks);
+    let mut padded = bytes.clone();
+    padded.resize((bytes.len() + 3) / 4 * 4, 0);
+    fletcher.update_with_slice_reflected(
+        &padded
+            .chunks(4)
+            .map(|chunk| match Endianness::DEFAULT {
+                Endianness::Little => LittleEndian::read_u32(chunk),
+                Endianness::Big => BigEndian::read_u32(chunk),
+            })
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(configured.value(), fletcher.value());
+
+    // Modular, seeded, big-endian and reflected all at once.
+    let mut configured = FletcherBuilder::new()
+        .seed(3, 5)
+        .endianness(Endianness::Big)
+        .modular()
+        .reflected()
+        .build::<u32>();
+    configured.update_with_bytes(&bytes);
+    let mut modular = ModularFletcher::<u32>::with_initial_values(3, 5);
+    padded.resize((bytes.len() + 1) / 2 * 2, 0);
+    modular.update_with_iter_scalar(
+        padded
+            .chunks(2)
+            .map(|chunk| BigEndian::read_u16(chunk).reverse_bits()),
+    );
+    assert_eq!(configured.value(), modular.value());
+
+    // Modular byte blocks, where the byte order does not matter.
+    let mut configured = FletcherBuilder::new().modular().build::<u16>();
+    configured.update_with_slice(&bytes);
+    let mut modular = ModularFletcher::<u16>::new();
+    modular.update_with_slice(&bytes);
+    assert_eq!(configured.value(), modular.value());
+}
+
+#[test]
+#[should_panic(expected = "the seed must fit in the block type")]
+fn builder_rejects_oversized_seed() {
+    let _ = FletcherBuilder::new().seed(0x100, 0).build::<u16>();
+}
+
+#[test]
+fn vectored_same_as_concatenated() {
+    let mut rng = rand::thread_rng();
+    let size_range = Uniform::from(0..40);
+
+    macro_rules! check {
+        ($fletcher:ty) => {
+            for _ in 0..50 {
+                let pieces: Vec<Vec<u8>> = (0..rng.gen_range(0..8))
+                    .map(|_| {
+                        (0..size_range.sample(&mut rng))
+                            .map(|_| rng.gen())
+                            .collect()
+                    })
+                    .collect();
+                let bufs: Vec<&[u8]> = pieces.iter().map(Vec::as_slice).collect();
+                let concatenated = pieces.concat();
+
+                for endianness in [Endianness::Little, Endianness::Big] {
+                    let mut vectored = <$fletcher>::with_initial_values(7, 11);
+                    vectored.update_vectored(&bufs, endianness);
+
+                    let mut whole = <$fletcher>::with_initial_values(7, 11);
+                    whole.update_with_bytes(&concatenated, endianness);
+
+                    assert_eq!(vectored, whole, "mismatch on: {:?}", pieces);
+                }
+            }
+        };
+    }
+
+    check!(Fletcher16);
+    check!(Fletcher32);
+    check!(Fletcher64);
+    check!(Fletcher128);
+    check!(Fletcher256);
+}
+
+#[test]
+fn associated_constants() {
+    assert_eq!(Fletcher16::BLOCK_BYTES, 1);
+    assert_eq!(Fletcher32::BLOCK_BYTES, 2);
+    assert_eq!(Fletcher64::BLOCK_BYTES, 4);
+    assert_eq!(Fletcher128::BLOCK_BYTES, 8);
+
+    assert_eq!(Fletcher16::RESULT_BITS, 16);
+    assert_eq!(Fletcher32::RESULT_BITS, 32);
+    assert_eq!(Fletcher64::RESULT_BITS, 64);
+    assert_eq!(Fletcher128::RESULT_BITS, 128);
+
+    assert_eq!(
+        <u64 as FletcherChecksum>::BLOCK_BYTES,
+        Fletcher64::BLOCK_BYTES
+    );
+}
+
+#[test]
+fn dispatch_introspection() {
+    assert_eq!(Fletcher16::LANES, 32);
+    assert_eq!(Fletcher32::LANES, 16);
+    assert_eq!(Fletcher64::LANES, 8);
+    assert_eq!(Fletcher128::LANES, 4);
+
+    let features = fletcher_simd::dispatched_target_features();
+    assert!(
+        ["avx+avx2", "avx", "sse+sse2", "sse", "neon", "none"].contains(&features),
+        "unexpected features {}",
+        features
+    );
+
+    // SSE2 is part of the x86_64 baseline, so one of the x86 clones is always chosen.
+    if cfg!(target_arch = "x86_64") {
+        assert!(features.starts_with("avx") || features == "sse+sse2");
+    }
+}
+
+#[test]
+fn states_as_set_keys() {
+    use std::collections::HashSet;
+
+    let states: HashSet<Fletcher32> = [&[1, 2][..], &[2, 1], &[1, 2], &[], &[0, 3]]
+        .iter()
+        .map(|data| *Fletcher32::new().update(data))
+        .collect();
+
+    // `[1, 2]` appears twice, while `[2, 1]` and `[0, 3]` only share its `a`.
+    assert_eq!(states.len(), 4);
+    assert!(states.contains(&Fletcher32::with_initial_values(3, 4)));
+    assert!(states.contains(&Fletcher32::new()));
+}
+
+#[test]
+fn ordered_by_value() {
+    let mut states: Vec<Fletcher32> = ["b", "a", "ab", "ba", "", "zz"]
+        .iter()
+        .map(|data| {
+            let mut fletcher = Fletcher32::new();
+            fletcher.update_with_slice_le(data.as_bytes());
+            fletcher
+        })
+        .collect();
+    let mut by_value = states.clone();
+
+    states.sort();
+    by_value.sort_by_key(Fletcher32::value);
+    assert_eq!(states, by_value);
+
+    // A larger `b` wins even if `a` is smaller.
+    assert!(Fletcher32::with_initial_values(0, 2) > Fletcher32::with_initial_values(9, 1));
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn states_as_bytes() {
+    let states = [
+        Fletcher32::with_initial_values(0x0102, 0x0304),
+        Fletcher32::with_initial_values(0x0506, 0x0708),
+    ];
+
+    let bytes: &[u8] = bytemuck::cast_slice(&states);
+    let expected: Vec<u8> = [0x0102u16, 0x0304, 0x0506, 0x0708]
+        .iter()
+        .flat_map(|x| x.to_ne_bytes())
+        .collect();
+    assert_eq!(bytes, expected.as_slice());
+
+    let restored: &[Fletcher32] = bytemuck::cast_slice(bytes);
+    assert_eq!(restored, &states);
+
+    let zeroed: Fletcher64 = bytemuck::Zeroable::zeroed();
+    assert_eq!(zeroed, Fletcher64::new());
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary_same_as_arbitrary_sums() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    let mut rng = rand::thread_rng();
+
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty) => {
+            let bytes: Vec<u8> = (0..64).map(|_| rng.gen()).collect();
+
+            let fletcher = <$fletcher>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+            let (a, b) =
+                <($block_type, $block_type)>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+            assert_eq!(fletcher, <$fletcher>::with_initial_values(a, b));
+        };
+    }
+
+    check!(Fletcher16, u8);
+    check!(Fletcher32, u16);
+    check!(Fletcher64, u32);
+    check!(Fletcher128, u64);
+    check!(Fletcher256, u128);
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn zeroize_resets_state() {
+    use zeroize::Zeroize;
+
+    let mut fletcher = Fletcher256::new();
+    fletcher.update_with_slice(&[u128::MAX, 3]);
+    fletcher.zeroize();
+    assert_eq!(fletcher, Fletcher256::new());
+
+    let mut buffered = BufferedFletcher::<u32>::new();
+    buffered.update_with_iter(0..37u16);
+    buffered.zeroize();
+    buffered.update_with_iter(0..5u16);
+    let mut fletcher = Fletcher32::new();
+    fletcher.update_with_iter(0..5u16);
+    assert_eq!(buffered.value(), fletcher.value());
+
+    let mut streaming = StreamingFletcher::<u64>::new(Endianness::Big);
+    streaming.update_bytes(b"secret");
+    streaming.zeroize();
+    streaming.update_bytes(b"abcde");
+    let mut fletcher = Fletcher64::new();
+    fletcher.update_with_bytes(b"abcde", Endianness::Big);
+    assert_eq!(streaming.finalize(), fletcher.value());
+}
+
+#[test]
+fn lanes_same_as_default() {
+    let mut rng = rand::thread_rng();
+    let size_range = Uniform::from(0..513);
+
+    const NUM_ITERS: usize = 100;
+    for _ in 0..NUM_ITERS {
+        let size: usize = size_range.sample(&mut rng);
+        let data: Vec<u8> = (0..size).map(|_| rng.gen()).collect();
+        let seed = Fletcher16::with_initial_values(rng.gen(), rng.gen());
+
+        let mut expected = seed;
+        expected.update_with_iter_scalar(data.iter().copied());
+
+        macro_rules! check_lanes {
+            ($($lanes:literal),+) => {$(
+                let mut from_slice = seed;
+                from_slice.update_with_slice_lanes::<$lanes>(&data);
+                assert_eq!(expected, from_slice, "mismatch with {} lanes on: {:?}", $lanes, data);
+
+                let mut from_iter = seed;
+                from_iter.update_with_iter_lanes::<_, $lanes>(data.iter().copied());
+                assert_eq!(expected, from_iter, "mismatch with {} lanes on: {:?}", $lanes, data);
+            )+};
+        }
+
+        check_lanes!(1, 2, 4, 8, 16, 32, 64);
+    }
+}
+
+#[test]
+fn slice_same_regardless_of_alignment() {
+    let data: Vec<u64> = (0..300u64)
+        .map(|i| i.wrapping_mul(0x0123_4567_89AB_CDEF))
+        .collect();
+    let bytes: Vec<u8> = data.iter().flat_map(|x| x.to_le_bytes()).collect();
+
+    // Every start offset within a vector, so that the aligned middle begins at a different block.
+    for offset in 0..32 {
+        let slice = &bytes[offset..];
+        let mut simd = Fletcher16::new();
+        let mut scalar = Fletcher16::new();
+        simd.update_with_slice(slice);
+        scalar.update_with_iter_scalar(slice.iter().copied());
+        assert_eq!(simd, scalar, "Fletcher16 mismatch at offset {}", offset);
+    }
+
+    for offset in 0..4 {
+        let slice = &data[offset..];
+        let mut simd = Fletcher128::new();
+        let mut scalar = Fletcher128::new();
+        simd.update_with_slice(slice);
+        scalar.update_with_iter_scalar(slice.iter().copied());
+        assert_eq!(simd, scalar, "Fletcher128 mismatch at offset {}", offset);
+    }
+}
+
+#[test]
+fn simd_chunks_same_as_slice() {
+    let data: Vec<u16> = (0..256).map(|i| i * 257).collect();
+
+    let mut from_slice = Fletcher32::with_initial_values(7, 11);
+    from_slice.update_with_slice(&data);
+
+    let mut default_width = Fletcher32::with_initial_values(7, 11);
+    default_width.update_with_simd_chunks(data.chunks(16).map(Simd::<u16, 16>::from_slice));
+    assert_eq!(default_width, from_slice);
+
+    let mut narrow = Fletcher32::with_initial_values(7, 11);
+    narrow.update_with_simd_chunks(data.chunks(4).map(Simd::<u16, 4>::from_slice));
+    assert_eq!(narrow, from_slice);
+}
+
+#[test]
+fn strided_same_as_scalar_loop() {
+    let mut rng = rand::thread_rng();
+    let size_range = Uniform::from(0..1000);
+    let stride_range = Uniform::from(1..20);
+
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty) => {
+            for _ in 0..20 {
+                let size: usize = size_range.sample(&mut rng);
+                let stride: usize = stride_range.sample(&mut rng);
+                let data: Vec<$block_type> = (0..size).map(|_| rng.gen()).collect();
+
+                let mut strided = <$fletcher>::with_initial_values(7, 11);
+                strided.update_strided(&data, stride);
+
+                let mut scalar = <$fletcher>::with_initial_values(7, 11);
+                let mut i = 0;
+                while i < data.len() {
+                    scalar.update_with_iter_scalar(core::iter::once(data[i]));
+                    i += stride;
+                }
+
+                assert_eq!(strided, scalar, "stride {} mismatch on: {:?}", stride, data);
+            }
+
+            // A stride past the end only selects the first block.
+            let mut strided = <$fletcher>::new();
+            strided.update_strided(&[1, 2, 3], usize::MAX);
+            assert_eq!(strided, <$fletcher>::with_initial_values(1, 1));
+        };
+    }
+
+    check!(Fletcher16, u8);
+    check!(Fletcher32, u16);
+    check!(Fletcher64, u32);
+    check!(Fletcher128, u64);
+}
+
+#[test]
+fn first_divergence_same_as_scalar() {
+    let mut rng = rand::thread_rng();
+    let size_range = Uniform::from(0..200);
+
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty) => {
+            for _ in 0..100 {
+                let size: usize = size_range.sample(&mut rng);
+                let a: Vec<$block_type> = (0..size).map(|_| rng.gen()).collect();
+                let mut b = a.clone();
+
+                // Flip a block at a random position, possibly past the end so that none differs,
+                // then sometimes truncate `b` to cover slices of different lengths.
+                let flipped = rng.gen_range(0..size + 10);
+                if let Some(block) = b.get_mut(flipped) {
+                    *block ^= 1;
+                }
+                if rng.gen() {
+                    b.truncate(rng.gen_range(0..size + 1));
+                }
+
+                let expected = a
+                    .iter()
+                    .zip(&b)
+                    .position(|(x, y)| x != y)
+                    .or_else(|| (a.len() != b.len()).then(|| a.len().min(b.len())));
+                assert_eq!(<$fletcher>::first_divergence(&a, &b), expected);
+                assert_eq!(<$fletcher>::first_divergence(&b, &a), expected);
+            }
+        };
+    }
+
+    check!(Fletcher16, u8);
+    check!(Fletcher32, u16);
+    check!(Fletcher64, u32);
+    check!(Fletcher128, u64);
+    check!(Fletcher256, u128);
+}
+
+// Each block's weight in `b` is the number of blocks from it to the end, so resuming from the
+// state of a prefix gives the same sums as computing the weights from scratch.
+#[test]
+fn resumed_same_as_weighted_sum() {
+    let mut rng = rand::thread_rng();
+    let data: Vec<u16> = (0..100).map(|_| rng.gen()).collect();
+
+    let a = data.iter().fold(0u16, |a, &elem| a.wrapping_add(elem));
+    let b = data.iter().enumerate().fold(0u16, |b, (i, &elem)| {
+        b.wrapping_add(elem.wrapping_mul((data.len() - i) as u16))
+    });
+
+    for split in 0..=data.len() {
+        let mut resumed = Fletcher32::new();
+        resumed.update_with_slice(&data[..split]);
+        let (prior_a, prior_b) = resumed.components();
+
+        let mut restored = Fletcher32::with_initial_values(prior_a, prior_b);
+        restored.update_with_slice(&data[split..]);
+        assert_eq!(
+            restored.components(),
+            (a, b),
+            "mismatch resuming at {}",
+            split
+        );
+    }
+}
+
+/// Returns at most three bytes per read, and interrupts every other read, so that blocks are
+/// split across reads.
+struct TrickleReader<'a> {
+    data: &'a [u8],
+    interrupt: bool,
+}
+
+impl std::io::Read for TrickleReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        if self.interrupt {
+            return Err(std::io::ErrorKind::Interrupted.into());
+        }
+
+        let len = buf.len().min(self.data.len()).min(3);
+        buf[..len].copy_from_slice(&self.data[..len]);
+        self.data = &self.data[len..];
+        Ok(len)
+    }
+}
+
+#[test]
+fn bufread_same_as_in_memory() {
+    use std::io::{BufReader, Cursor};
+
+    let mut rng = rand::thread_rng();
+    let data: Vec<u8> = (0..10_007).map(|_| rng.gen()).collect();
+
+    macro_rules! check {
+        ($fletcher:ty) => {
+            for endianness in [Endianness::Little, Endianness::Big] {
+                let mut in_memory = <$fletcher>::new();
+                in_memory.update_with_bytes(&data, endianness);
+
+                // Capacities that split blocks across fills, as well as one larger than the input.
+                for capacity in [1, 3, 7, 17, 1000, 20_000] {
+                    let mut reader = BufReader::with_capacity(capacity, Cursor::new(&data));
+                    let mut fletcher = <$fletcher>::new();
+                    let consumed = fletcher
+                        .update_with_bufread(&mut reader, endianness)
+                        .unwrap();
+
+                    assert_eq!(consumed, data.len() as u64);
+                    assert_eq!(fletcher, in_memory, "mismatch with capacity {}", capacity);
+                }
+            }
+        };
+    }
+
+    check!(Fletcher16);
+    check!(Fletcher32);
+    check!(Fletcher64);
+    check!(Fletcher128);
+    check!(Fletcher256);
+}
+
+#[test]
+fn reader_same_as_in_memory() {
+    let mut rng = rand::thread_rng();
+    // Longer than the read buffer and not a multiple of any block size.
+    let data: Vec<u8> = (0..200_003).map(|_| rng.gen()).collect();
+
+    macro_rules! check {
+        ($fletcher:ty) => {
+            let mut in_memory = <$fletcher>::new();
+            in_memory.update_with_bytes(&data, Endianness::DEFAULT);
+
+            let mut trickled = <$fletcher>::new();
+            trickled
+                .update_with_reader(
+                    TrickleReader {
+                        data: &data,
+                        interrupt: false,
+                    },
+                    Endianness::DEFAULT,
+                )
+                .unwrap();
+            assert_eq!(trickled, in_memory);
+        };
+    }
+
+    check!(Fletcher16);
+    check!(Fletcher32);
+    check!(Fletcher64);
+    check!(Fletcher128);
+    check!(Fletcher256);
+}
+
+// WASI runtimes grant no directories by default, and `temp_dir` panics there.
+#[cfg(not(target_os = "wasi"))]
+#[test]
+fn file_same_as_in_memory() {
+    let mut rng = rand::thread_rng();
+    // Longer than the read buffer and not a multiple of any block size.
+    let data: Vec<u8> = (0..200_003).map(|_| rng.gen()).collect();
+
+    let path = std::env::temp_dir().join(format!(
+        "fletcher-simd-test-{}-{}",
+        std::process::id(),
+        rng.gen::<u64>()
+    ));
+    std::fs::write(&path, &data).unwrap();
+
+    macro_rules! check {
+        ($fletcher:ty, $file_fn:ident) => {
+            let mut in_memory = <$fletcher>::new();
+            in_memory.update_with_bytes(&data, Endianness::DEFAULT);
+
+            assert_eq!($file_fn(&path).unwrap(), in_memory.value());
+        };
+    }
+
+    check!(Fletcher16, fletcher16_file);
+    check!(Fletcher32, fletcher32_file);
+    check!(Fletcher64, fletcher64_file);
+    check!(Fletcher128, fletcher128_file);
+    check!(Fletcher256, fletcher256_file);
+
+    std::fs::remove_file(&path).unwrap();
+    assert!(fletcher16_file(&path).is_err());
+}
+
+#[test]
+fn empty_iter_leaves_checksum_unchanged() {
+    macro_rules! check {
+        ($fletcher:ty, $block_type:ty) => {
+            let seeded = <$fletcher>::with_initial_values(7, 11);
+
+            // Both an iterator that is known to be empty and one that only turns out to be.
+            let mut fletcher = seeded;
+            fletcher.update_with_iter(core::iter::empty::<$block_type>());
+            assert_eq!(fletcher, seeded);
+
+            fletcher.update_with_iter((0..100 as $block_type).filter(|_| false));
+            assert_eq!(fletcher, seeded);
+
+            fletcher.update_with_iter_scalar(core::iter::empty::<$block_type>());
+            assert_eq!(fletcher, seeded);
+
+            fletcher.update_with_slice(&[]);
+            assert_eq!(fletcher, seeded);
+        };
+    }
+
+    check!(Fletcher16, u8);
+    check!(Fletcher32, u16);
+    check!(Fletcher64, u32);
+    check!(Fletcher128, u64);
+    check!(Fletcher256, u128);
+}
+
+/// Passes through an iterator while claiming more elements than it has.
+struct OverstatedLen<I>(I);
+
+impl<I: Iterator> Iterator for OverstatedLen<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, _) = self.0.size_hint();
+        (lower + 40, None)
+    }
+}
+
+#[test]
+fn sized_iter_same_as_unsized() {
+    let mut rng = rand::thread_rng();
+    let size_range = Uniform::from(0..300);
+
+    const NUM_ITERS: usize = 100;
+    for _ in 0..NUM_ITERS {
+        let size: usize = size_range.sample(&mut rng);
+        let data: Vec<u8> = (0..size).map(|_| rng.gen()).collect();
+
+        let mut from_slice = Fletcher16::with_initial_values(7, 11);
+        from_slice.update_with_slice(&data);
+
+        let mut sized = Fletcher16::with_initial_values(7, 11);
+        sized.update_with_iter(data.iter().copied());
+        assert_eq!(sized, from_slice, "exact size mismatch on: {:?}", data);
+
+        let mut unsized_iter = Fletcher16::with_initial_values(7, 11);
+        unsized_iter.update_with_iter(data.iter().copied().filter(|_| true));
+        assert_eq!(unsized_iter, from_slice, "unsized mismatch on: {:?}", data);
+
+        // Only part of the data has a known length, so both paths are taken.
+        let mut chained = Fletcher16::with_initial_values(7, 11);
+        chained.update_with_iter(
+            data[..size / 2]
+                .iter()
+                .chain(data[size / 2..].iter().filter(|_| true))
+                .copied(),
+        );
+        assert_eq!(chained, from_slice, "chained mismatch on: {:?}", data);
+
+        let mut overstated = Fletcher16::with_initial_values(7, 11);
+        overstated.update_with_iter(OverstatedLen(data.iter().copied()));
+        assert_eq!(overstated, from_slice, "overstated mismatch on: {:?}", data);
+    }
+}
+
+#[test]
+fn tee_same_as_update_with_iter() {
+    let mut rng = rand::thread_rng();
+    let size_range = Uniform::from(0..129);
+
+    const NUM_ITERS: usize = 100;
+    for _ in 0..NUM_ITERS {
+        let size: usize = size_range.sample(&mut rng);
+        let data: Vec<u32> = (0..size).map(|_| rng.gen()).collect();
+
+        let mut tee = data.iter().copied().fletcher_tee::<u64>();
+        let passed_through: Vec<u32> = tee.by_ref().collect();
+
+        let mut fletcher = Fletcher64::new();
+        fletcher.update_with_iter(data.iter().copied());
+
+        assert_eq!(passed_through, data);
+        assert_eq!(tee.value(), fletcher.value(), "mismatch on: {:?}", data);
+    }
+}
+
+#[test]
+fn adler32_zlib_vectors() {
+    const VECTORS: [(&str, u32); 8] = [
+        ("", 0x00000001),
+        ("a", 0x00620062),
+        ("abc", 0x024D0127),
+        ("message digest", 0x29750586),
+        ("abcdefghijklmnopqrstuvwxyz", 0x90860B20),
+        (
+            "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+            0x8ADB150C,
+        ),
+        (
+            "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+            0x97B61069,
+        ),
+        ("Wikipedia", 0x11E60398),
+    ];
+
+    for (data, expected) in VECTORS {
+        let mut adler = Adler32::new();
+        adler.update_with_slice(data.as_bytes());
+
+        assert_eq!(adler.value(), expected, "mismatch on: {:?}", data);
+    }
+}
+
+#[test]
+fn adler32_long_input() {
+    // Long enough to need several reductions, with every byte at its maximum.
+    let data = vec![0xFF; 100_000];
+    let mut adler = Adler32::new();
+
+    let (first, second) = data.split_at(12_345);
+    adler.update_with_slice(first);
+    adler.update_with_slice(second);
+
+    assert_eq!(adler.value(), 0x149A302C);
+}
+
+#[cfg(feature = "experimental")]
+#[test]
+fn reducer_matches_classic_fletcher32() {
+    fn classic_fletcher32(data: &[u16]) -> u32 {
+        let (mut a, mut b) = (0u64, 0u64);
+        for &elem in data {
+            a = (a + elem as u64) % 65535;
+            b = (b + a) % 65535;
+        }
+        ((b << 16) | a) as u32
+    }
+
+    let mut rng = rand::thread_rng();
+    let size_range = Uniform::from(0..2049);
+
+    const NUM_ITERS: usize = 100;
+    for _ in 0..NUM_ITERS {
+        let size: usize = size_range.sample(&mut rng);
+        let data: Vec<u16> = (0..size).map(|_| rng.gen()).collect();
+
+        let mut fletcher = Fletcher32::with_reducer(|x| (x % 65535) as u16);
+        let (first, second) = data.split_at(rng.gen_range(0..=size));
+        fletcher.update_with_slice(first);
+        fletcher.update_with_slice(second);
+
+        assert_eq!(
+            fletcher.value(),
+            classic_fletcher32(&data),
+            "mismatch on: {:?}",
+            data
+        );
+    }
+
+    // The published Fletcher-32 test vectors, read as little-endian blocks with zero padding.
+    const VECTORS: [(&str, u32); 3] = [
+        ("abcde", 0xF04FC729),
+        ("abcdef", 0x56502D2A),
+        ("abcdefgh", 0xEBE19591),
+    ];
+
+    for (data, expected) in VECTORS {
+        let mut padded = data.as_bytes().to_vec();
+        padded.resize((padded.len() + 1) / 2 * 2, 0);
+        let blocks: Vec<u16> = padded.chunks(2).map(LittleEndian::read_u16).collect();
+
+        let mut fletcher = Fletcher32::with_reducer(|x| (x % 65535) as u16);
+        fletcher.update_with_slice(&blocks);
+
+        assert_eq!(fletcher.value(), expected, "mismatch on: {:?}", data);
+    }
+}
+
+#[test]
+fn rolling_same_as_recomputed() {
+    let mut rng = rand::thread_rng();
+
+    const NUM_ITERS: usize = 20;
+    for _ in 0..NUM_ITERS {
+        let data: Vec<u8> = (0..512).map(|_| rng.gen()).collect();
+        let window_len = rng.gen_range(1..=64);
+
+        let mut rolling = RollingFletcher::<u16>::new(&data[..window_len]);
+        for start in 1..=(data.len() - window_len) {
+            rolling.roll(data[start - 1], data[start + window_len - 1], window_len);
+
+            let mut fletcher = Fletcher16::new();
+            fletcher.update_with_slice(&data[start..start + window_len]);
+
+            assert_eq!(
+                rolling.value(),
+                fletcher.value(),
+                "mismatch at window {} with length {}",
+                start,
+                window_len
+            );
+        }
+    }
+}
+
+#[cfg(feature = "maybe_uninit")]
+#[test]
+fn maybe_uninit_same_as_initialized() {
+    let mut rng = rand::thread_rng();
+
+    let mut buf = [core::mem::MaybeUninit::<u8>::uninit(); 256];
+    let init_len = rng.gen_range(0..=buf.len());
+    let data: Vec<u8> = (0..init_len).map(|_| rng.gen()).collect();
+    for (slot, &elem) in buf.iter_mut().zip(&data) {
+        slot.write(elem);
+    }
+
+    let mut from_uninit = Fletcher16::new();
+    // SAFETY: The first `init_len` elements were written above.
+    unsafe { from_uninit.update_with_maybe_uninit(&buf, init_len) };
+
+    let mut from_init = Fletcher16::new();
+    from_init.update_with_slice(&data);
+
+    assert_eq!(from_uninit, from_init, "mismatch on: {:?}", data);
+}
+
+#[cfg(feature = "maybe_uninit")]
+#[test]
+#[should_panic(expected = "initialized length 5 exceeds buffer length 4")]
+fn maybe_uninit_rejects_overlong_length() {
+    let buf = [core::mem::MaybeUninit::<u8>::new(0); 4];
+
+    // SAFETY: Every element is initialized, so only the bounds check can fail.
+    unsafe { Fletcher16::new().update_with_maybe_uninit(&buf, 5) };
 }
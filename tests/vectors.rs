@@ -0,0 +1,130 @@
+//! Canonical test vectors for every width.
+//!
+//! Inputs are byte strings. For the wider widths, every group of bytes is read as a little-endian
+//! block and a trailing partial block is zero-padded, matching
+//! [`update_with_bytes`](fletcher_simd::Fletcher::update_with_bytes). The expected values were
+//! computed with an independent scalar implementation and must not change between releases or
+//! platforms.
+
+use fletcher_simd::{
+    Endianness, Fletcher128, Fletcher16, Fletcher256, Fletcher32, Fletcher64, U256,
+};
+
+/// Generates `len` pseudo-random bytes from a fixed xorshift32 sequence, taking the top byte of
+/// each state.
+fn pseudo_random(len: usize) -> Vec<u8> {
+    let mut state: u32 = 0x2545F491;
+
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state >> 24) as u8
+        })
+        .collect()
+}
+
+/// Expected Fletcher-16, -32, -64, -128, and -256 values.
+type Expected = (u16, u32, u64, u128, U256);
+
+/// Returns each named input along with its expected values.
+fn vectors() -> Vec<(&'static str, Vec<u8>, Expected)> {
+    vec![
+        ("empty", vec![], (0x0, 0x0, 0x0, 0x0, U256::from(0x0))),
+        (
+            "single byte",
+            vec![0x61],
+            (
+                0x6161,
+                0x610061,
+                0x6100000061,
+                0x610000000000000061,
+                U256::from_words(0x61, 0x61),
+            ),
+        ),
+        (
+            "all zeros",
+            vec![0x00; 1000],
+            (0x0, 0x0, 0x0, 0x0, U256::from(0x0)),
+        ),
+        (
+            "all 0xFF",
+            vec![0xFF; 1000],
+            (
+                0xEC18,
+                0x16BEFE0C,
+                0xFFFF8571FFFFFF06,
+                0xFFFFFFFFFFFFE13DFFFFFFFFFFFFFF83,
+                U256::from_words(0xFFFFFFFFFFFFF820, 0xFFFFFFFFFFFFFFC1),
+            ),
+        ),
+        (
+            "ascending",
+            (0..1000).map(|i| i as u8).collect(),
+            (
+                0x042C,
+                0x87C4829C,
+                0x52480774BCC1C654,
+                0x8EACCAE9072535E0D95BDE60E365E830,
+                U256::from_words(
+                    0xFE173049627B94AEAEC6DEF70F273C00,
+                    0xF1B37536F8BA7C3EE7A86929EAAB6C10,
+                ),
+            ),
+        ),
+        (
+            "pseudo-random",
+            pseudo_random(8191),
+            (
+                0x8602,
+                0x3EF325E0,
+                0x5783E5E8C42365B3,
+                0xEEC8A621180D7A28B62030EB0E0336D3,
+                U256::from_words(
+                    0x57767849F8BA74266FA3A8FB3218D2D8,
+                    0x9F6B9C693D97041F16B49481D06C33BD,
+                ),
+            ),
+        ),
+    ]
+}
+
+/// Splits bytes into zero-padded, little-endian blocks of `N` bytes.
+fn blocks<const N: usize>(data: &[u8]) -> impl Iterator<Item = [u8; N]> + '_ {
+    data.chunks(N).map(|chunk| {
+        let mut block = [0; N];
+        block[..chunk.len()].copy_from_slice(chunk);
+        block
+    })
+}
+
+macro_rules! test_vectors {
+    ($test_name:ident, $fletcher:ty, $block_type:ty, $index:tt) => {
+        #[test]
+        fn $test_name() {
+            for (name, data, expected) in vectors() {
+                let decoded: Vec<$block_type> =
+                    blocks(&data).map(<$block_type>::from_le_bytes).collect();
+
+                let mut from_bytes = <$fletcher>::new();
+                from_bytes.update_with_bytes(&data, Endianness::Little);
+                assert_eq!(from_bytes.value(), expected.$index, "bytes: {}", name);
+
+                let mut simd = <$fletcher>::new();
+                simd.update_with_slice(&decoded);
+                assert_eq!(simd.value(), expected.$index, "SIMD: {}", name);
+
+                let mut scalar = <$fletcher>::new();
+                scalar.update_with_iter_scalar(decoded.iter().copied());
+                assert_eq!(scalar.value(), expected.$index, "scalar: {}", name);
+            }
+        }
+    };
+}
+
+test_vectors!(vectors_fletcher16, Fletcher16, u8, 0);
+test_vectors!(vectors_fletcher32, Fletcher32, u16, 1);
+test_vectors!(vectors_fletcher64, Fletcher64, u32, 2);
+test_vectors!(vectors_fletcher128, Fletcher128, u64, 3);
+test_vectors!(vectors_fletcher256, Fletcher256, u128, 4);